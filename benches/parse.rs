@@ -0,0 +1,67 @@
+//! Parsing benchmarks for large mazes.
+//!
+//! Run with `cargo bench`. Each line reports the mean time to parse a fully
+//! walled `n`x`n` maze, so the growth between sizes shows how parsing scales.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use maze_parse::{Maze, SyntaxError};
+
+const SIZES: [usize; 5] = [25, 50, 100, 200, 500];
+const TARGET_TIME: Duration = Duration::from_millis(500);
+
+/// Builds an `n`x`n` small format maze with every wall present.
+fn sm_maze(n: usize) -> String {
+    let border = format!("+{}", "---+".repeat(n));
+    let inner = format!("|{}", "   |".repeat(n));
+
+    let mut lines = vec![border.clone()];
+    for _ in 0..n {
+        lines.push(inner.clone());
+        lines.push(border.clone());
+    }
+    lines.join("\n")
+}
+
+/// Builds an `n`x`n` large format maze with every wall present.
+fn lg_maze(n: usize) -> String {
+    let border = "+---+".repeat(n);
+    let inner = "|   |".repeat(n);
+
+    let mut lines = Vec::new();
+    for _ in 0..n {
+        lines.push(border.clone());
+        lines.push(inner.clone());
+        lines.push(border.clone());
+    }
+    lines.join("\n")
+}
+
+/// Returns the mean duration of `f`, running it until `TARGET_TIME` elapses.
+fn measure(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    let mut iters = 0;
+    while iters == 0 || start.elapsed() < TARGET_TIME {
+        f();
+        iters += 1;
+    }
+    start.elapsed() / iters
+}
+
+fn bench(name: &str, make: fn(usize) -> String, parse: fn(&str) -> Result<Maze, SyntaxError>) {
+    for n in SIZES {
+        let input = make(n);
+        let time = measure(|| {
+            black_box(parse(black_box(&input)).unwrap());
+        });
+        println!("{:<9} {:>4}x{:<4} {:>12.3?}", name, n, n, time);
+    }
+}
+
+fn main() {
+    bench("parse_sm", sm_maze, |s| Maze::parse_sm(s));
+    bench("parse_lg", lg_maze, |s| Maze::parse_lg(s));
+}
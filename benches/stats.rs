@@ -0,0 +1,57 @@
+//! Statistics benchmarks for large mazes with loops.
+//!
+//! Run with `cargo bench`. Each line reports the mean time to measure a generated
+//! `n`x`n` maze with loops carved into it, so the growth between sizes shows how the
+//! diameter estimate scales. The exact diameter is only measured for the smaller sizes.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use maze_parse::{Direction, Generator, Maze, MazeStats, Pos};
+
+const SIZES: [usize; 5] = [25, 50, 100, 200, 500];
+const EXACT_SIZES: [usize; 2] = [25, 50];
+const TARGET_TIME: Duration = Duration::from_millis(500);
+
+/// Generates an `n`x`n` maze and opens some of its walls, giving it loops.
+fn looped_maze(n: usize) -> Maze {
+    let mut maze = Maze::generate(n, n, Generator::Kruskal, 1);
+    for r in (0..n).step_by(7) {
+        for c in (0..n - 1).step_by(5) {
+            maze.carve(Pos::new(r, c), Direction::Right).unwrap();
+        }
+    }
+    maze
+}
+
+/// Returns the mean duration of `f`, running it until `TARGET_TIME` elapses.
+fn measure(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    let mut iters = 0;
+    while iters == 0 || start.elapsed() < TARGET_TIME {
+        f();
+        iters += 1;
+    }
+    start.elapsed() / iters
+}
+
+fn bench(name: &str, sizes: &[usize], stats: fn(&Maze) -> MazeStats) {
+    for &n in sizes {
+        let maze = looped_maze(n);
+        let time = measure(|| {
+            black_box(stats(black_box(&maze)));
+        });
+        println!("{:<19} {:>4}x{:<4} {:>12.3?}", name, n, n, time);
+    }
+}
+
+fn main() {
+    bench("stats", &SIZES, Maze::stats);
+    bench(
+        "stats_with_diameter",
+        &EXACT_SIZES,
+        Maze::stats_with_diameter,
+    );
+}
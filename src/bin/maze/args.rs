@@ -0,0 +1,255 @@
+use std::fmt;
+
+use maze_parse::{Generator, MazeFormat, MergePolicy, Pos};
+
+use crate::errors::CliError;
+
+pub const USAGE: &str = "\
+Usage: maze <command> [options] [FILE...]
+
+Commands:
+  parse      Parse mazes and print their structure
+  validate   Check that mazes parse, reporting any errors
+  explain    Explain why mazes fail to parse, with snippets and hints
+  render     Render mazes back to ASCII
+  convert    Convert mazes between formats
+  solve      Find the shortest path through a maze
+  stats      Print statistics about mazes
+  check      Check that mazes are connected and perfect (no loops)
+  generate   Generate a new maze
+
+Options:
+  -f, --format <json|debug|ascii>  Output format (default: debug, or ascii for generate)
+      --strict                     Reject mazes whose neighbouring cells disagree (validate)
+      --exact                      Measure the exact diameter of mazes with loops, which is
+                                   slow for large mazes (stats)
+      --to <sm|lg|box>             Format to convert to (convert)
+      --merge <union|intersection|error>
+                                   How to merge disagreeing shared walls (convert, default: union)
+      --start <row,col|LABEL>      Cell to start from (solve, check, default: the `S` cell, or
+                                   the top left)
+      --goal <row,col|LABEL>       Cell to reach (solve, default: the nearest `E` or `G` cell,
+                                   or the bottom right)
+      --size <WxH>                 Size of the maze in cells (generate, default: 10x10)
+      --algorithm <NAME>           backtracker, kruskal, prim, wilson, eller or binary-tree
+                                   (generate, default: backtracker)
+      --seed <N>                   Seed for the random numbers (generate, default: 0)
+  -h, --help                       Print this help
+
+FILE may be `-` to read from stdin. Stdin is read when no FILE is given.
+
+Exit codes:
+  0  success
+  1  a maze failed to parse, validate, convert, solve or check
+  2  invalid command line
+  3  an input could not be read";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Command {
+    Parse,
+    Validate,
+    Explain,
+    Render,
+    Convert,
+    Solve,
+    Stats,
+    Check,
+    Generate,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "parse" => Some(Self::Parse),
+            "validate" => Some(Self::Validate),
+            "explain" => Some(Self::Explain),
+            "render" => Some(Self::Render),
+            "convert" => Some(Self::Convert),
+            "solve" => Some(Self::Solve),
+            "stats" => Some(Self::Stats),
+            "check" => Some(Self::Check),
+            "generate" => Some(Self::Generate),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Validate => "validate",
+            Self::Explain => "explain",
+            Self::Render => "render",
+            Self::Convert => "convert",
+            Self::Solve => "solve",
+            Self::Stats => "stats",
+            Self::Check => "check",
+            Self::Generate => "generate",
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    Json,
+    #[default]
+    Debug,
+    Ascii,
+}
+
+impl OutputFormat {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(Self::Json),
+            "debug" => Some(Self::Debug),
+            "ascii" => Some(Self::Ascii),
+            _ => None,
+        }
+    }
+}
+
+fn maze_format_from_name(name: &str) -> Option<MazeFormat> {
+    match name {
+        "sm" => Some(MazeFormat::Small),
+        "lg" => Some(MazeFormat::Large),
+        "box" => Some(MazeFormat::BoxDrawing),
+        _ => None,
+    }
+}
+
+fn merge_policy_from_name(name: &str) -> Option<MergePolicy> {
+    match name {
+        "union" => Some(MergePolicy::Union),
+        "intersection" => Some(MergePolicy::Intersection),
+        "error" => Some(MergePolicy::Error),
+        _ => None,
+    }
+}
+
+fn size_from_str(s: &str) -> Option<(usize, usize)> {
+    let (width, height) = s.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+/// A cell named on the command line, by coordinate or by the text inside it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Endpoint {
+    Pos(Pos),
+    Label(String),
+}
+
+impl Endpoint {
+    fn from_arg(arg: String) -> Self {
+        let pos = arg.split_once(',').and_then(|(row, col)| {
+            Some(Pos::new(row.trim().parse().ok()?, col.trim().parse().ok()?))
+        });
+        match pos {
+            Some(pos) => Self::Pos(pos),
+            None => Self::Label(arg),
+        }
+    }
+}
+
+/// Options shared by every command. Each command reads the ones it needs.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub format: Option<OutputFormat>,
+    pub strict: bool,
+    pub exact: bool,
+    pub to: Option<MazeFormat>,
+    pub merge: MergePolicy,
+    pub start: Option<Endpoint>,
+    pub goal: Option<Endpoint>,
+    pub size: Option<(usize, usize)>,
+    pub generator: Generator,
+    pub seed: u64,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum Args {
+    Help,
+    Run { command: Command, options: Options },
+}
+
+impl Args {
+    /// Parses the command line arguments, excluding the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut args = args.into_iter();
+
+        let command = match args.next() {
+            None => return Err(CliError::Usage(String::from("missing command"))),
+            Some(arg) if arg == "-h" || arg == "--help" => return Ok(Self::Help),
+            Some(arg) => Command::from_name(&arg)
+                .ok_or_else(|| CliError::Usage(format!("unknown command `{}`", arg)))?,
+        };
+
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| CliError::Usage(format!("missing value for `{}`", arg)))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Self::Help),
+                "-f" | "--format" => {
+                    let value = value()?;
+                    options.format =
+                        Some(OutputFormat::from_name(&value).ok_or_else(|| {
+                            CliError::Usage(format!("unknown format `{}`", value))
+                        })?);
+                }
+                "--strict" => options.strict = true,
+                "--exact" => options.exact = true,
+                "--to" => {
+                    let value = value()?;
+                    options.to = Some(maze_format_from_name(&value).ok_or_else(|| {
+                        CliError::Usage(format!("unknown maze format `{}`", value))
+                    })?);
+                }
+                "--merge" => {
+                    let value = value()?;
+                    options.merge = merge_policy_from_name(&value).ok_or_else(|| {
+                        CliError::Usage(format!("unknown merge policy `{}`", value))
+                    })?;
+                }
+                "--start" => options.start = Some(Endpoint::from_arg(value()?)),
+                "--goal" => options.goal = Some(Endpoint::from_arg(value()?)),
+                "--size" => {
+                    let value = value()?;
+                    options.size = Some(size_from_str(&value).ok_or_else(|| {
+                        CliError::Usage(format!("expected a size like `10x10`, found `{}`", value))
+                    })?);
+                }
+                "--algorithm" => {
+                    let value = value()?;
+                    options.generator = Generator::from_name(&value)
+                        .ok_or_else(|| CliError::Usage(format!("unknown algorithm `{}`", value)))?;
+                }
+                "--seed" => {
+                    let value = value()?;
+                    options.seed = value.parse().map_err(|_| {
+                        CliError::Usage(format!(
+                            "expected a number for `--seed`, found `{}`",
+                            value
+                        ))
+                    })?;
+                }
+                "-" => options.paths.push(arg),
+                _ if arg.starts_with('-') => {
+                    return Err(CliError::Usage(format!("unknown option `{}`", arg)));
+                }
+                _ => options.paths.push(arg),
+            }
+        }
+
+        Ok(Self::Run { command, options })
+    }
+}
@@ -0,0 +1,289 @@
+use std::io::{self, Write};
+
+use maze_parse::{Generator, Maze, MazeFormat, MergePolicy, Path, Pos};
+
+use crate::{
+    args::{Command, Endpoint, Options, OutputFormat},
+    errors::CliError,
+    input::{read_inputs, Input},
+    output::{format_maze, format_stats},
+};
+
+/// Runs `command`, printing its output to stdout.
+pub fn run(command: Command, options: &Options) -> Result<(), CliError> {
+    let paths = &options.paths;
+    let out = &mut io::stdout().lock();
+    match command {
+        Command::Parse => parse(out, options.format.unwrap_or_default(), paths),
+        Command::Validate => validate(out, paths, options.strict),
+        Command::Explain => explain(out, paths),
+        Command::Render => render(out, paths),
+        Command::Convert => {
+            let to = options
+                .to
+                .ok_or_else(|| CliError::Usage(String::from("`convert` needs `--to`")))?;
+            convert(out, paths, to, options.merge)
+        }
+        Command::Solve => solve(out, paths, options.start.as_ref(), options.goal.as_ref()),
+        Command::Generate => {
+            if !paths.is_empty() {
+                return Err(CliError::Usage(String::from("`generate` takes no inputs")));
+            }
+            let (width, height) = options.size.unwrap_or((10, 10));
+            generate(
+                out,
+                width,
+                height,
+                options.generator,
+                options.seed,
+                options.format.unwrap_or(OutputFormat::Ascii),
+            )
+        }
+        Command::Stats => stats(out, paths, options.format, options.exact),
+        Command::Check => check(out, paths, options.start.as_ref()),
+    }
+}
+
+fn parse_input(input: &Input) -> Result<Maze, CliError> {
+    input
+        .content
+        .parse::<Maze>()
+        .map_err(|err| CliError::Parse {
+            name: input.name.clone(),
+            err: Box::new(err),
+        })
+}
+
+fn parse(out: &mut impl Write, format: OutputFormat, paths: &[String]) -> Result<(), CliError> {
+    for input in read_inputs(paths)? {
+        let maze = parse_input(&input)?;
+        writeln!(out, "{}", format_maze(&maze, format)?)?;
+    }
+    Ok(())
+}
+
+fn render(out: &mut impl Write, paths: &[String]) -> Result<(), CliError> {
+    for input in read_inputs(paths)? {
+        let maze = parse_input(&input)?;
+        writeln!(out, "{}", maze)?;
+    }
+    Ok(())
+}
+
+fn convert(
+    out: &mut impl Write,
+    paths: &[String],
+    to: MazeFormat,
+    merge: MergePolicy,
+) -> Result<(), CliError> {
+    for input in read_inputs(paths)? {
+        let maze = parse_input(&input)?;
+        let converted = maze.convert(to, merge).map_err(|err| CliError::Convert {
+            name: input.name.clone(),
+            err,
+        })?;
+        for conflict in &converted.conflicts {
+            eprintln!(
+                "{}: warning: {} (merged by {})",
+                input.name, conflict, merge
+            );
+        }
+        writeln!(out, "{}", converted.value)?;
+    }
+    Ok(())
+}
+
+/// Looks up a cell named on the command line in the maze read from `name`.
+fn find_endpoint(maze: &Maze, name: &str, endpoint: &Endpoint) -> Result<Pos, CliError> {
+    match endpoint {
+        Endpoint::Pos(pos) => match maze.get(*pos) {
+            Some(_) => Ok(*pos),
+            None => Err(CliError::OutOfBounds {
+                name: String::from(name),
+                pos: *pos,
+            }),
+        },
+        Endpoint::Label(label) => maze.find_label(label).ok_or_else(|| CliError::NoSuchLabel {
+            name: String::from(name),
+            label: label.clone(),
+        }),
+    }
+}
+
+fn solve(
+    out: &mut impl Write,
+    paths: &[String],
+    start: Option<&Endpoint>,
+    goal: Option<&Endpoint>,
+) -> Result<(), CliError> {
+    for input in read_inputs(paths)? {
+        let maze = parse_input(&input)?;
+        let find = |endpoint| find_endpoint(&maze, &input.name, endpoint);
+
+        // Without endpoints on the command line, use the maze's own markers
+        let start = match start {
+            Some(endpoint) => find(endpoint)?,
+            None => maze.start().unwrap_or_default(),
+        };
+        let goals = match goal {
+            Some(endpoint) => vec![find(endpoint)?],
+            None => match maze.goals() {
+                goals if goals.is_empty() => vec![Pos::new(
+                    maze.height().saturating_sub(1),
+                    maze.width().saturating_sub(1),
+                )],
+                goals => goals,
+            },
+        };
+
+        let path = goals
+            .iter()
+            .filter_map(|&goal| maze.solve(start, goal))
+            .min_by_key(Path::len)
+            .ok_or_else(|| CliError::Unsolvable {
+                name: input.name.clone(),
+                start,
+                goals,
+            })?;
+        writeln!(out, "{}", maze.overlay_path(&path))?;
+    }
+    Ok(())
+}
+
+fn generate(
+    out: &mut impl Write,
+    width: usize,
+    height: usize,
+    generator: Generator,
+    seed: u64,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let maze = Maze::generate(width, height, generator, seed);
+    writeln!(out, "{}", format_maze(&maze, format)?)?;
+    Ok(())
+}
+
+fn stats(
+    out: &mut impl Write,
+    paths: &[String],
+    format: Option<OutputFormat>,
+    exact: bool,
+) -> Result<(), CliError> {
+    for input in read_inputs(paths)? {
+        let maze = parse_input(&input)?;
+        let stats = if exact {
+            maze.stats_with_diameter()
+        } else {
+            maze.stats()
+        };
+        writeln!(out, "{}", format_stats(&input.name, &stats, format))?;
+    }
+    Ok(())
+}
+
+fn check(out: &mut impl Write, paths: &[String], start: Option<&Endpoint>) -> Result<(), CliError> {
+    let inputs = read_inputs(paths)?;
+    let mut failed = 0;
+
+    for input in &inputs {
+        let maze = parse_input(input)?;
+        if maze.is_perfect() {
+            writeln!(out, "{}: perfect", input.name)?;
+            continue;
+        }
+        failed += 1;
+
+        let components = maze.components();
+        let cells = components.iter().map(Vec::len).sum::<usize>();
+        let loops = (maze.passage_count() + components.len()).saturating_sub(cells);
+        writeln!(
+            out,
+            "{}: not perfect: {} component{}, {} loop{}",
+            input.name,
+            components.len(),
+            if components.len() == 1 { "" } else { "s" },
+            loops,
+            if loops == 1 { "" } else { "s" },
+        )?;
+
+        let from = match start {
+            Some(endpoint) => find_endpoint(&maze, &input.name, endpoint)?,
+            None => maze.start().unwrap_or_default(),
+        };
+        let unreachable = maze.unreachable_cells(from);
+        if !unreachable.is_empty() {
+            let cells = unreachable.iter().map(Pos::to_string).collect::<Vec<_>>();
+            writeln!(out, "  unreachable from {}: {}", from, cells.join(", "))?;
+        }
+    }
+
+    if failed > 0 {
+        return Err(CliError::Imperfect {
+            failed,
+            total: inputs.len(),
+        });
+    }
+    Ok(())
+}
+
+fn validate(out: &mut impl Write, paths: &[String], strict: bool) -> Result<(), CliError> {
+    let inputs = read_inputs(paths)?;
+    let mut failed = 0;
+
+    for input in &inputs {
+        let findings = match parse_input(input) {
+            Ok(maze) => maze.validate(),
+            Err(err) => {
+                writeln!(out, "{}", err)?;
+                failed += 1;
+                continue;
+            }
+        };
+
+        // Without `--strict`, inconsistencies are reported but don't fail the maze
+        if !strict {
+            for finding in &findings {
+                eprintln!("{}: warning: {}", input.name, finding);
+            }
+            writeln!(out, "{}: ok", input.name)?;
+        } else if findings.is_empty() {
+            writeln!(out, "{}: ok", input.name)?;
+        } else {
+            for finding in findings {
+                writeln!(out, "{}: {}", input.name, finding)?;
+            }
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(CliError::Invalid {
+            failed,
+            total: inputs.len(),
+        });
+    }
+    Ok(())
+}
+
+fn explain(out: &mut impl Write, paths: &[String]) -> Result<(), CliError> {
+    let inputs = read_inputs(paths)?;
+    let mut failed = 0;
+
+    for input in &inputs {
+        match input.content.parse::<Maze>() {
+            Ok(_) => writeln!(out, "{}: ok", input.name)?,
+            Err(err) => {
+                writeln!(out, "{}:\n{}", input.name, err.explain(&input.content))?;
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(CliError::Unparsable {
+            failed,
+            total: inputs.len(),
+        });
+    }
+    Ok(())
+}
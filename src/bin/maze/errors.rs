@@ -0,0 +1,103 @@
+use std::{fmt, io, process::ExitCode};
+
+use maze_parse::{ConvertError, ParseMazeError, Pos};
+
+pub enum CliError {
+    /// The command line could not be understood.
+    Usage(String),
+    /// An input could not be read.
+    Io { name: String, err: io::Error },
+    /// Output could not be written to stdout.
+    Output(io::Error),
+    /// An input was read but is not a valid maze.
+    Parse {
+        name: String,
+        err: Box<ParseMazeError>,
+    },
+    /// A maze could not be converted without losing walls.
+    Convert { name: String, err: ConvertError },
+    /// There is no path between the requested cells.
+    Unsolvable {
+        name: String,
+        start: Pos,
+        goals: Vec<Pos>,
+    },
+    /// No cell has the requested label.
+    NoSuchLabel { name: String, label: String },
+    /// A requested cell is outside the maze.
+    OutOfBounds { name: String, pos: Pos },
+    /// Some of the checked mazes are not perfect.
+    Imperfect { failed: usize, total: usize },
+    /// Some of the validated inputs are not valid mazes.
+    Invalid { failed: usize, total: usize },
+    /// Some of the explained inputs are not valid mazes.
+    Unparsable { failed: usize, total: usize },
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Parse { .. }
+            | Self::Convert { .. }
+            | Self::Unsolvable { .. }
+            | Self::NoSuchLabel { .. }
+            | Self::OutOfBounds { .. }
+            | Self::Imperfect { .. }
+            | Self::Invalid { .. }
+            | Self::Unparsable { .. } => ExitCode::from(1),
+            Self::Usage(_) => ExitCode::from(2),
+            Self::Io { .. } | Self::Output(_) => ExitCode::from(3),
+        }
+    }
+}
+
+impl CliError {
+    /// Returns whether stdout was closed early, e.g. by `maze render | head`, which is not
+    /// worth reporting.
+    pub fn is_broken_pipe(&self) -> bool {
+        matches!(self, Self::Output(err) if err.kind() == io::ErrorKind::BrokenPipe)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        Self::Output(err)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Usage(msg) => write!(f, "{}", msg),
+            Self::Io { name, err } => write!(f, "{}: {}", name, err),
+            Self::Output(err) => write!(f, "stdout: {}", err),
+            Self::Parse { name, err } => write!(f, "{}: {}", name, err),
+            Self::Convert { name, err } => write!(f, "{}: {}", name, err),
+            Self::Unsolvable { name, start, goals } => {
+                let goals = goals.iter().map(Pos::to_string).collect::<Vec<_>>();
+                write!(
+                    f,
+                    "{}: no path from {} to {}",
+                    name,
+                    start,
+                    goals.join(" or ")
+                )
+            }
+            Self::NoSuchLabel { name, label } => {
+                write!(f, "{}: no cell is labelled `{}`", name, label)
+            }
+            Self::OutOfBounds { name, pos } => {
+                write!(f, "{}: cell {} is outside the maze", name, pos)
+            }
+            Self::Imperfect { failed, total } => {
+                write!(f, "{} of {} mazes are not perfect", failed, total)
+            }
+            Self::Invalid { failed, total } => {
+                write!(f, "{} of {} mazes failed validation", failed, total)
+            }
+            Self::Unparsable { failed, total } => {
+                write!(f, "{} of {} mazes failed to parse", failed, total)
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+use crate::errors::CliError;
+
+/// The name used for stdin in messages.
+const STDIN_NAME: &str = "<stdin>";
+
+/// A named maze source read from a file or stdin.
+pub struct Input {
+    pub name: String,
+    pub content: String,
+}
+
+/// Reads every path in `paths`, treating `-` (or an empty list) as stdin.
+pub fn read_inputs(paths: &[String]) -> Result<Vec<Input>, CliError> {
+    if paths.is_empty() {
+        return Ok(vec![read_stdin()?]);
+    }
+
+    paths
+        .iter()
+        .map(|path| {
+            if path == "-" {
+                return read_stdin();
+            }
+            fs::read_to_string(path)
+                .map(|content| Input {
+                    name: path.clone(),
+                    content,
+                })
+                .map_err(|err| CliError::Io {
+                    name: path.clone(),
+                    err,
+                })
+        })
+        .collect()
+}
+
+fn read_stdin() -> Result<Input, CliError> {
+    let mut content = String::new();
+    io::stdin()
+        .read_to_string(&mut content)
+        .map_err(|err| CliError::Io {
+            name: String::from(STDIN_NAME),
+            err,
+        })?;
+
+    Ok(Input {
+        name: String::from(STDIN_NAME),
+        content,
+    })
+}
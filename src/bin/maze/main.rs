@@ -0,0 +1,40 @@
+mod args;
+mod commands;
+mod errors;
+mod input;
+mod output;
+
+use std::{
+    env,
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use crate::{
+    args::{Args, USAGE},
+    errors::CliError,
+};
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) if err.is_broken_pipe() => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("maze: {}", err);
+            if let CliError::Usage(_) = err {
+                eprintln!("\n{}", USAGE);
+            }
+            err.exit_code()
+        }
+    }
+}
+
+fn run() -> Result<(), CliError> {
+    match Args::parse(env::args().skip(1))? {
+        Args::Help => {
+            writeln!(io::stdout().lock(), "{}", USAGE)?;
+            Ok(())
+        }
+        Args::Run { command, options } => commands::run(command, &options),
+    }
+}
@@ -0,0 +1,97 @@
+use maze_parse::{Cell, Maze, MazeStats};
+
+use crate::{args::OutputFormat, errors::CliError};
+
+/// Formats a maze for printing in the requested format.
+pub fn format_maze(maze: &Maze, format: OutputFormat) -> Result<String, CliError> {
+    match format {
+        OutputFormat::Json => Ok(maze_to_json(maze)),
+        OutputFormat::Debug => Ok(format!("{:#?}", maze)),
+        OutputFormat::Ascii => Ok(maze.to_string()),
+    }
+}
+
+/// Formats the stats of the maze read from `name`. Text is the default, as for ASCII output.
+pub fn format_stats(name: &str, stats: &MazeStats, format: Option<OutputFormat>) -> String {
+    match format {
+        Some(OutputFormat::Json) => stats_to_json(name, stats),
+        Some(OutputFormat::Debug) => format!("{}: {:#?}", name, stats),
+        Some(OutputFormat::Ascii) | None => format!("{}:\n{}", name, stats),
+    }
+}
+
+fn stats_to_json(name: &str, stats: &MazeStats) -> String {
+    let or_null = |value: Option<String>| value.unwrap_or_else(|| String::from("null"));
+
+    format!(
+        "{{\"name\":{},\"width\":{},\"height\":{},\"cells\":{},\"isolated\":{},\
+\"dead_ends\":{},\"corridors\":{},\"t_junctions\":{},\"crossroads\":{},\"passages\":{},\
+\"components\":{},\"loops\":{},\"diameter\":{},\"diameter_exact\":{},\"solution_length\":{},\"difficulty\":{}}}",
+        json_string(name),
+        stats.width,
+        stats.height,
+        stats.cells,
+        stats.isolated,
+        stats.dead_ends,
+        stats.corridors,
+        stats.t_junctions,
+        stats.crossroads,
+        stats.passages,
+        stats.components,
+        stats.loops,
+        stats.diameter,
+        stats.diameter_exact,
+        or_null(stats.solution_length.map(|len| len.to_string())),
+        or_null(stats.difficulty.map(|difficulty| difficulty.to_string())),
+    )
+}
+
+fn maze_to_json(maze: &Maze) -> String {
+    let rows = maze
+        .cells
+        .iter()
+        .map(|row| {
+            let cells = row.iter().map(cell_to_json).collect::<Vec<_>>().join(",");
+            format!("[{}]", cells)
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("{{\"cells\":[{}]}}", rows)
+}
+
+fn cell_to_json(cell: &Cell) -> String {
+    format!(
+        "{{\"wall_top\":{},\"wall_bottom\":{},\"wall_left\":{},\"wall_right\":{},\
+\"corner_top_left\":{},\"corner_top_right\":{},\"corner_bottom_left\":{},\"corner_bottom_right\":{},\
+\"inner_text\":{}}}",
+        cell.wall_top,
+        cell.wall_bottom,
+        cell.wall_left,
+        cell.wall_right,
+        cell.corner_top_left,
+        cell.corner_top_right,
+        cell.corner_bottom_left,
+        cell.corner_bottom_right,
+        json_string(&cell.inner_text),
+    )
+}
+
+/// Quotes and escapes `s` as a JSON string literal.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
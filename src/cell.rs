@@ -1,6 +1,13 @@
 use std::str::FromStr;
 
-use anyhow::Error;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    charset::Charset,
+    errors::{Span, SyntaxError},
+    parser::ContentParser,
+    pos::{Corner, Direction, Pos},
+};
 
 // x (cols)
 pub const WALL_DASHES: usize = 3;
@@ -10,7 +17,181 @@ pub const CELL_CHAR_WIDTH: usize = WALL_DASHES + 2;
 pub const CELL_WALL_PIPES: usize = 1;
 pub const CELL_LINE_HEIGHT: usize = CELL_WALL_PIPES + 2;
 
+/// The interior size of every cell in a maze.
+///
+/// The default matches the classic `+---+` cell: three columns wide and one line tall.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CellGeometry {
+    /// Columns between the left and right walls (the number of dashes in a wall).
+    pub width: usize,
+    /// Lines between the top and bottom walls (the number of pipes in a wall).
+    pub height: usize,
+}
+
+impl Default for CellGeometry {
+    fn default() -> Self {
+        Self::new(WALL_DASHES, CELL_WALL_PIPES)
+    }
+}
+
+impl CellGeometry {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height }
+    }
+
+    /// Returns the width of a cell in columns, including its left and right walls.
+    pub fn char_width(&self) -> usize {
+        self.width + 2
+    }
+
+    /// Returns the height of a cell in lines, including its top and bottom walls.
+    pub fn line_height(&self) -> usize {
+        self.height + 2
+    }
+
+    /// Infers the geometry of the maze in `s` from its first row of corners.
+    ///
+    /// The width is the smallest non-zero gap between neighbouring corners on the first line,
+    /// and the height is the smallest gap between one of those corners and the next
+    /// corner below it. Either falls back to the default when the corners needed
+    /// are missing.
+    ///
+    /// Box-drawing glyphs that are plain lines (`─`, `│`) are skipped, since they are
+    /// indistinguishable from walls. A box-drawing maze whose first line has no junctions
+    /// to measure is sized from the glyphs of the whole maze instead, as in `from_strides`.
+    pub fn infer(s: &str, charset: Charset) -> Self {
+        if charset == Charset::BoxDrawing {
+            if let Some(geometry) = Self::from_strides(s, charset) {
+                return geometry;
+            }
+        }
+
+        let mut parser = ContentParser::new(s);
+        let first_line_len = parser.line_len(1).unwrap_or(0);
+        let line_count = parser.line_count();
+
+        let mut is_corner = |line, col| {
+            parser.go_to(line, col).is_ok_and(|c| {
+                charset.is_corner(c)
+                    && !charset.is_horizontal_wall(c)
+                    && !charset.is_vertical_wall(c)
+            })
+        };
+
+        let corner_cols = (1..=first_line_len)
+            .filter(|&col| is_corner(1, col))
+            .collect::<Vec<_>>();
+
+        let width = corner_cols
+            .windows(2)
+            .map(|pair| pair[1] - pair[0] - 1)
+            // Skip the doubled `++` corners of the large format
+            .filter(|&gap| gap > 0)
+            .min()
+            .unwrap_or(WALL_DASHES);
+
+        let height = corner_cols
+            .iter()
+            .filter_map(|&col| (2..=line_count).find(|&line| is_corner(line, col)))
+            .map(|line| line - 2)
+            .min()
+            .unwrap_or(CELL_WALL_PIPES);
+
+        Self::new(width, height)
+    }
+
+    /// Finds the cell size whose grid lines fit every wall and corner glyph in `s`.
+    ///
+    /// Box-drawing mazes draw a plain `─` or `│` where walls meet in a straight line, so a
+    /// row of cells open to each other has no junctions to measure. Every column holding a
+    /// `│` or a junction must still lie on a grid line, as must every line holding a `─` or
+    /// a junction. Each stride dividing those offsets is tried, the default first and then
+    /// the widest, and the first that puts only walls and corners on the grid lines wins.
+    fn from_strides(s: &str, charset: Charset) -> Option<Self> {
+        let is_junction = |c| {
+            charset.is_corner(c) && !charset.is_horizontal_wall(c) && !charset.is_vertical_wall(c)
+        };
+
+        let (mut col_stride, mut line_stride) = (0, 0);
+        for_each_column(s, |line, col, c| {
+            if is_junction(c) || charset.is_vertical_wall(c) {
+                col_stride = gcd(col_stride, col);
+            }
+            if is_junction(c) || charset.is_horizontal_wall(c) {
+                line_stride = gcd(line_stride, line);
+            }
+        });
+
+        let fits = |col_stride: usize, line_stride: usize| {
+            let mut fits = true;
+            for_each_column(s, |line, col, c| {
+                fits &= c == ' '
+                    || match (
+                        line.is_multiple_of(line_stride),
+                        col.is_multiple_of(col_stride),
+                    ) {
+                        (true, true) => charset.is_corner(c),
+                        (true, false) => charset.is_horizontal_wall(c),
+                        (false, true) => charset.is_vertical_wall(c),
+                        (false, false) => true,
+                    };
+            });
+            fits
+        };
+
+        let default = Self::default();
+        for col_stride in candidate_strides(col_stride, default.width + 1) {
+            for line_stride in candidate_strides(line_stride, default.height + 1) {
+                if fits(col_stride, line_stride) {
+                    return Some(Self::new(col_stride - 1, line_stride - 1));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Calls `f` with the zero-based line and display column of every character in `s`.
+/// The column after a wide character is visited as a `\0`.
+fn for_each_column(s: &str, mut f: impl FnMut(usize, usize, char)) {
+    for (line, text) in s.lines().enumerate() {
+        let mut col = 0;
+        for c in text.chars() {
+            let width = UnicodeWidthChar::width(c).unwrap_or(0);
+            if width > 0 {
+                f(line, col, c);
+            }
+            for extra in 1..width {
+                f(line, col + extra, '\0');
+            }
+            col += width;
+        }
+    }
+}
+
+/// Returns the strides of at least 2 that divide `offsets`, `preferred` first and then
+/// from widest to narrowest.
+fn candidate_strides(offsets: usize, preferred: usize) -> Vec<usize> {
+    let mut strides = (2..=offsets)
+        .rev()
+        .filter(|stride| offsets.is_multiple_of(*stride))
+        .collect::<Vec<_>>();
+    if let Some(index) = strides.iter().position(|&stride| stride == preferred) {
+        strides.remove(index);
+        strides.insert(0, preferred);
+    }
+    strides
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        b => gcd(b, a % b),
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cell {
     // Walls
     pub wall_top: bool,
@@ -28,23 +209,121 @@ pub struct Cell {
     pub inner_text: String,
 }
 
-impl FromStr for Cell {
-    type Err = Error;
+impl Cell {
+    /// Parses a single cell drawn with the given charset.
+    ///
+    /// The cell's geometry is taken from `s` itself: the width of its first line and
+    /// the number of lines. Inner text spanning several lines is joined with `\n`.
+    ///
+    /// Errors are reported relative to the cell, with its top left corner at line 1,
+    /// column 1 and a cell coordinate of `(0, 0)`.
+    pub fn parse_with(s: &str, charset: Charset) -> Result<Self, SyntaxError> {
+        // Columns are display columns, so wide inner text keeps the walls aligned
+        let mut parser = ContentParser::new(s);
+        let cell = Pos::default();
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let lines: Vec<&str> = s.lines().collect();
+        let bottom = parser.line_count();
+        let right = parser.line_len(1).unwrap_or(0);
+        if bottom < 3 || right < 2 {
+            return Err(SyntaxError::TruncatedCell {
+                span: Span::new(1, 1, right.max(1)),
+                cell,
+            });
+        }
+
+        for line in 2..=bottom {
+            let found = parser.line_len(line).unwrap_or(0);
+            if found != right {
+                return Err(SyntaxError::RaggedRow {
+                    span: Span::new(line, found.min(right) + 1, found.max(right)),
+                    cell,
+                    expected: right,
+                    found,
+                });
+            }
+        }
 
-        let wall_top = lines[0].chars().nth(1) == Some('-');
-        let wall_bottom = lines[2].chars().nth(1) == Some('-');
-        let wall_left = lines[1].chars().nth(0) == Some('|');
-        let wall_right = lines[1].chars().nth(4) == Some('|');
+        let mut char_at = |line, col| {
+            parser
+                .go_to(line, col)
+                .expect("cell lines should all be `right` columns wide")
+        };
 
-        let corner_top_left = lines[0].chars().nth(0) == Some('+');
-        let corner_top_right = lines[0].chars().nth(4) == Some('+');
-        let corner_bottom_left = lines[2].chars().nth(0) == Some('+');
-        let corner_bottom_right = lines[2].chars().nth(4) == Some('+');
+        let mut corner = |line, col| {
+            let c = char_at(line, col);
+            if c != ' ' && !charset.is_corner(c) {
+                return Err(SyntaxError::UnexpectedChar {
+                    span: Span::at(line, col),
+                    cell,
+                    found: c,
+                    expected: "a corner or a space",
+                });
+            }
+            Ok(c != ' ')
+        };
 
-        let inner_text: String = lines[1].chars().skip(1).take(WALL_DASHES).collect();
+        let corner_top_left = corner(1, 1)?;
+        let corner_top_right = corner(1, right)?;
+        let corner_bottom_left = corner(bottom, 1)?;
+        let corner_bottom_right = corner(bottom, right)?;
+
+        let mut wall = |side, is_wall: fn(Charset, char) -> bool| {
+            let (positions, span): (Vec<(usize, usize)>, _) = match side {
+                Direction::Up => ((2..right).map(|col| (1, col)).collect(), None),
+                Direction::Down => ((2..right).map(|col| (bottom, col)).collect(), None),
+                Direction::Left => ((2..bottom).map(|line| (line, 1)).collect(), Some(1)),
+                Direction::Right => ((2..bottom).map(|line| (line, right)).collect(), Some(right)),
+            };
+
+            let mut first = None;
+            for (line, col) in positions {
+                let c = char_at(line, col);
+                let is_wall = is_wall(charset, c);
+                if !is_wall && c != ' ' {
+                    return Err(SyntaxError::UnexpectedChar {
+                        span: Span::at(line, col),
+                        cell,
+                        found: c,
+                        expected: "a wall or a space",
+                    });
+                }
+
+                match first {
+                    None => first = Some(is_wall),
+                    Some(first) if first != is_wall => {
+                        return Err(SyntaxError::InconsistentWall {
+                            // Underline the whole of a horizontal wall, or the
+                            // first line that breaks a vertical one
+                            span: match span {
+                                Some(col) => Span::at(line, col),
+                                None => Span::new(line, 2, right - 1),
+                            },
+                            cell,
+                            side,
+                        });
+                    }
+                    Some(_) => {}
+                }
+            }
+            Ok(first.unwrap_or(false))
+        };
+
+        let wall_top = wall(Direction::Up, Charset::is_horizontal_wall)?;
+        let wall_bottom = wall(Direction::Down, Charset::is_horizontal_wall)?;
+        let wall_left = wall(Direction::Left, Charset::is_vertical_wall)?;
+        let wall_right = wall(Direction::Right, Charset::is_vertical_wall)?;
+
+        // The side walls were checked to be narrow characters, so the inner text
+        // always starts and ends on a character boundary
+        let inner_text = (2..bottom)
+            .map(|line| {
+                parser
+                    .go_to(line, 2)
+                    .and_then(|_| parser.slice(right - 2))
+                    .expect("inner text should sit between narrow wall characters")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
 
         Ok(Cell {
             wall_top,
@@ -58,4 +337,61 @@ impl FromStr for Cell {
             inner_text,
         })
     }
+
+    /// Returns the geometry implied by the cell's inner text.
+    pub fn geometry(&self) -> CellGeometry {
+        let lines = self.inner_text.split('\n');
+        CellGeometry::new(
+            lines.clone().next().map_or(0, UnicodeWidthStr::width),
+            lines.count(),
+        )
+    }
+
+    /// Returns whether the cell has a wall on `side`.
+    pub fn has_wall(&self, side: Direction) -> bool {
+        match side {
+            Direction::Up => self.wall_top,
+            Direction::Right => self.wall_right,
+            Direction::Down => self.wall_bottom,
+            Direction::Left => self.wall_left,
+        }
+    }
+
+    /// Adds or removes the wall on `side`. The neighbouring cell is left alone.
+    pub fn set_wall(&mut self, side: Direction, present: bool) {
+        match side {
+            Direction::Up => self.wall_top = present,
+            Direction::Right => self.wall_right = present,
+            Direction::Down => self.wall_bottom = present,
+            Direction::Left => self.wall_left = present,
+        }
+    }
+
+    /// Returns whether the cell has `corner`.
+    pub fn has_corner(&self, corner: Corner) -> bool {
+        match corner {
+            Corner::TopLeft => self.corner_top_left,
+            Corner::TopRight => self.corner_top_right,
+            Corner::BottomLeft => self.corner_bottom_left,
+            Corner::BottomRight => self.corner_bottom_right,
+        }
+    }
+
+    /// Adds or removes `corner`. Neighbouring cells sharing the point are left alone.
+    pub fn set_corner(&mut self, corner: Corner, present: bool) {
+        match corner {
+            Corner::TopLeft => self.corner_top_left = present,
+            Corner::TopRight => self.corner_top_right = present,
+            Corner::BottomLeft => self.corner_bottom_left = present,
+            Corner::BottomRight => self.corner_bottom_right = present,
+        }
+    }
+}
+
+impl FromStr for Cell {
+    type Err = SyntaxError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse_with(s, Charset::Ascii)
+    }
 }
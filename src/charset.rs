@@ -0,0 +1,81 @@
+/// The characters a maze is drawn with.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Charset {
+    /// `+` corners, `-` horizontal walls and `|` vertical walls.
+    #[default]
+    Ascii,
+    /// Unicode box-drawing characters, such as `┌─┬─┐`, `│`, `├`, `┼` and `└─┴─┘`.
+    BoxDrawing,
+}
+
+/// The walls leading away from a corner, used to pick a box-drawing glyph.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Arms {
+    pub up: bool,
+    pub right: bool,
+    pub down: bool,
+    pub left: bool,
+}
+
+/// Light box-drawing glyphs indexed by `up | right << 1 | down << 2 | left << 3`.
+/// A corner without any walls is drawn as a middle dot.
+const BOX_CORNERS: [char; 16] = [
+    '·', '╵', '╶', '└', '╷', '│', '┌', '├', '╴', '┘', '─', '┴', '┐', '┤', '┬', '┼',
+];
+
+impl Charset {
+    pub fn is_horizontal_wall(self, c: char) -> bool {
+        match self {
+            Self::Ascii => c == '-',
+            Self::BoxDrawing => matches!(c, '─' | '━' | '═'),
+        }
+    }
+
+    pub fn is_vertical_wall(self, c: char) -> bool {
+        match self {
+            Self::Ascii => c == '|',
+            Self::BoxDrawing => matches!(c, '│' | '┃' | '║'),
+        }
+    }
+
+    /// Returns whether `c` marks a corner. Any box-drawing character counts as a
+    /// corner in that charset, since the glyph depends on the surrounding walls.
+    pub fn is_corner(self, c: char) -> bool {
+        match self {
+            Self::Ascii => c == '+',
+            Self::BoxDrawing => c == BOX_CORNERS[0] || is_box_drawing(c),
+        }
+    }
+
+    pub fn horizontal_wall(self) -> char {
+        match self {
+            Self::Ascii => '-',
+            Self::BoxDrawing => '─',
+        }
+    }
+
+    pub fn vertical_wall(self) -> char {
+        match self {
+            Self::Ascii => '|',
+            Self::BoxDrawing => '│',
+        }
+    }
+
+    /// Returns the glyph for a corner with the given walls leading away from it.
+    pub fn corner(self, arms: Arms) -> char {
+        match self {
+            Self::Ascii => '+',
+            Self::BoxDrawing => {
+                let i = arms.up as usize
+                    | (arms.right as usize) << 1
+                    | (arms.down as usize) << 2
+                    | (arms.left as usize) << 3;
+                BOX_CORNERS[i]
+            }
+        }
+    }
+}
+
+fn is_box_drawing(c: char) -> bool {
+    ('\u{2500}'..='\u{257F}').contains(&c)
+}
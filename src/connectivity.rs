@@ -0,0 +1,126 @@
+use crate::{maze::Maze, pos::Pos};
+
+impl Maze {
+    /// Returns whether every cell can be reached from every other. An empty maze counts
+    /// as connected.
+    pub fn is_connected(&self) -> bool {
+        self.components().len() <= 1
+    }
+
+    /// Returns whether there is exactly one route between any two cells: the maze is
+    /// connected and has no loops.
+    pub fn is_perfect(&self) -> bool {
+        self.is_connected() && self.passage_count() + 1 == self.iter_row_major().count().max(1)
+    }
+
+    /// Returns the cells that can't be reached from `from`, in row-major order. Every cell
+    /// is unreachable from a position outside the maze.
+    pub fn unreachable_cells(&self, from: Pos) -> Vec<Pos> {
+        if self.get(from).is_none() {
+            return self.iter_row_major().map(|(pos, _)| pos).collect();
+        }
+
+        let ids = self.component_ids();
+        let id = ids[from.row][from.col];
+        self.iter_row_major()
+            .map(|(pos, _)| pos)
+            .filter(|pos| ids[pos.row][pos.col] != id)
+            .collect()
+    }
+
+    /// Splits the maze into groups of cells that can reach one another, each in row-major
+    /// order. Groups are ordered by their first cell, so the one holding the top left cell
+    /// comes first.
+    pub fn components(&self) -> Vec<Vec<Pos>> {
+        let mut components = Vec::<Vec<Pos>>::new();
+        let ids = self.component_ids();
+        for (pos, _) in self.iter_row_major() {
+            let id = ids[pos.row][pos.col];
+            if id == components.len() {
+                components.push(Vec::new());
+            }
+            components[id].push(pos);
+        }
+        components
+    }
+
+    /// Returns the number of open passages between neighbouring cells, each counted once.
+    /// Together with `components` it gives the number of loops, `passages - cells +
+    /// components`.
+    pub fn passage_count(&self) -> usize {
+        self.iter_row_major()
+            .map(|(pos, _)| {
+                // Count each passage from the cell above or left of it
+                self.open_neighbors(pos)
+                    .filter(|(_, next)| *next > pos)
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Labels every cell with the number of its connected component, numbered from 0 in
+    /// row-major order of each component's first cell.
+    pub(crate) fn component_ids(&self) -> Vec<Vec<usize>> {
+        let mut ids = self
+            .cells
+            .iter()
+            .map(|row| vec![usize::MAX; row.len()])
+            .collect::<Vec<_>>();
+
+        let mut bfs = Bfs::new(self);
+        let mut next_id = 0;
+        for (pos, _) in self.iter_row_major() {
+            if ids[pos.row][pos.col] != usize::MAX {
+                continue;
+            }
+            for &(reached, _) in bfs.run(pos) {
+                ids[reached.row][reached.col] = next_id;
+            }
+            next_id += 1;
+        }
+        ids
+    }
+}
+
+/// A breadth-first search over open passages that keeps its buffers between runs, so
+/// searching from many cells costs only the cells each run reaches.
+pub(crate) struct Bfs<'a> {
+    maze: &'a Maze,
+    /// The number of the last run to reach each cell.
+    seen: Vec<Vec<usize>>,
+    runs: usize,
+    reached: Vec<(Pos, usize)>,
+}
+
+impl<'a> Bfs<'a> {
+    pub(crate) fn new(maze: &'a Maze) -> Self {
+        Self {
+            maze,
+            seen: maze.cells.iter().map(|row| vec![0; row.len()]).collect(),
+            runs: 0,
+            reached: Vec::new(),
+        }
+    }
+
+    /// Returns every cell reachable from `start` with its distance in moves, in the order
+    /// the search reaches them, so the last is as far from `start` as any.
+    pub(crate) fn run(&mut self, start: Pos) -> &[(Pos, usize)] {
+        self.runs += 1;
+        self.reached.clear();
+        self.reached.push((start, 0));
+        self.seen[start.row][start.col] = self.runs;
+
+        // The cells reached so far double as the queue
+        let mut next = 0;
+        while let Some(&(pos, distance)) = self.reached.get(next) {
+            next += 1;
+            for (_, neighbor) in self.maze.open_neighbors(pos) {
+                if self.seen[neighbor.row][neighbor.col] != self.runs {
+                    self.seen[neighbor.row][neighbor.col] = self.runs;
+                    self.reached.push((neighbor, distance + 1));
+                }
+            }
+        }
+        &self.reached
+    }
+}
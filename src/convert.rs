@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::{
+    format::MazeFormat,
+    maze::Maze,
+    pos::{Corner, Pos},
+    validate::Inconsistency,
+};
+
+/// How to merge the two sides of a shared wall when neighbouring cells disagree about it.
+///
+/// Only the small and box-drawing formats need merging, since they draw each shared wall
+/// once. The large format draws both sides, so converting to it never loses anything.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MergePolicy {
+    /// Keep the wall if either cell has it.
+    #[default]
+    Union,
+    /// Keep the wall only if both cells have it.
+    Intersection,
+    /// Refuse to convert, reporting every conflicting wall.
+    Error,
+}
+
+impl MergePolicy {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Union => "union",
+            Self::Intersection => "intersection",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for MergePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The result of a conversion, along with the disagreements between neighbouring cells
+/// that the `MergePolicy` resolved to produce it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Converted<T> {
+    pub value: T,
+    /// The `Inconsistency::OneSidedWall` and `Inconsistency::MissingCorner` findings that
+    /// were merged away, in row-major order. Empty if the cells already agreed.
+    pub conflicts: Vec<Inconsistency>,
+}
+
+/// Neighbouring cells disagree about shared walls or corners, and the policy was
+/// `MergePolicy::Error`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConvertError {
+    conflicts: Vec<Inconsistency>,
+}
+
+impl ConvertError {
+    /// Returns the conflicts, each a `Inconsistency::OneSidedWall` reported on the cell that
+    /// has the wall or a `Inconsistency::MissingCorner` reported on the cell that lacks the
+    /// corner.
+    pub fn conflicts(&self) -> &[Inconsistency] {
+        &self.conflicts
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Neighbouring cells disagree in {} place{}.",
+            self.conflicts.len(),
+            if self.conflicts.len() == 1 { "" } else { "s" },
+        )?;
+        for conflict in &self.conflicts {
+            write!(f, " {}", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl Maze {
+    /// Renders the maze in `format`, merging shared walls and corners according to `policy`
+    /// where the format draws them once.
+    ///
+    /// The conflicts the policy resolved come back with the text. The large format draws
+    /// both sides of every wall, so converting to it never has any.
+    pub fn convert(
+        &self,
+        format: MazeFormat,
+        policy: MergePolicy,
+    ) -> Result<Converted<String>, ConvertError> {
+        if format == MazeFormat::Large {
+            return Ok(Converted {
+                value: self.render_lg(),
+                conflicts: Vec::new(),
+            });
+        }
+
+        let Converted { value, conflicts } = self.merge_walls(policy)?;
+        let value = match format {
+            MazeFormat::BoxDrawing => value.render_box(),
+            _ => value.render_sm(),
+        };
+        Ok(Converted { value, conflicts })
+    }
+
+    /// Returns a copy of the maze in which neighbouring cells agree on every shared wall
+    /// and corner, resolving disagreements according to `policy`.
+    pub fn merge_walls(&self, policy: MergePolicy) -> Result<Converted<Maze>, ConvertError> {
+        let conflicts = self.conflicts();
+        if policy == MergePolicy::Error && !conflicts.is_empty() {
+            return Err(ConvertError { conflicts });
+        }
+
+        let merge = |sides: &[bool]| match policy {
+            MergePolicy::Intersection => sides.iter().all(|&side| side),
+            _ => sides.iter().any(|&side| side),
+        };
+
+        let mut maze = self.clone();
+        for r in 0..maze.cells.len() {
+            for c in 0..maze.cells[r].len() {
+                if c + 1 < maze.cells[r].len() {
+                    let wall =
+                        merge(&[maze.cells[r][c].wall_right, maze.cells[r][c + 1].wall_left]);
+                    maze.cells[r][c].wall_right = wall;
+                    maze.cells[r][c + 1].wall_left = wall;
+                }
+                if c < maze.cells.get(r + 1).map_or(0, Vec::len) {
+                    let wall =
+                        merge(&[maze.cells[r][c].wall_bottom, maze.cells[r + 1][c].wall_top]);
+                    maze.cells[r][c].wall_bottom = wall;
+                    maze.cells[r + 1][c].wall_top = wall;
+                }
+            }
+        }
+
+        // Each corner point is shared by up to four cells
+        for r in 0..=maze.cells.len() {
+            for c in 0..=maze.width() {
+                let sharing = [
+                    (r.checked_sub(1), c.checked_sub(1), Corner::BottomRight),
+                    (r.checked_sub(1), Some(c), Corner::BottomLeft),
+                    (Some(r), c.checked_sub(1), Corner::TopRight),
+                    (Some(r), Some(c), Corner::TopLeft),
+                ]
+                .into_iter()
+                .filter_map(|(r, c, corner)| Some((Pos::new(r?, c?), corner)))
+                .filter(|&(pos, _)| maze.get(pos).is_some())
+                .collect::<Vec<_>>();
+
+                let present = merge(
+                    &sharing
+                        .iter()
+                        .map(|&(pos, corner)| maze[pos].has_corner(corner))
+                        .collect::<Vec<_>>(),
+                );
+                for (pos, corner) in sharing {
+                    if let Some(cell) = maze.get_mut(pos) {
+                        cell.set_corner(corner, present);
+                    }
+                }
+            }
+        }
+
+        Ok(Converted {
+            value: maze,
+            conflicts,
+        })
+    }
+
+    /// Returns the disagreements between neighbouring cells that converting has to resolve:
+    /// the `OneSidedWall` and `MissingCorner` findings of `validate`, in row-major order.
+    pub(crate) fn conflicts(&self) -> Vec<Inconsistency> {
+        self.validate()
+            .into_iter()
+            .filter(|finding| !matches!(finding, Inconsistency::WallWithoutCorners { .. }))
+            .collect()
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt::Write;
+
+use crate::{
+    cell::CellGeometry,
+    errors::{ParseMazeError, SyntaxError},
+    format::{detect_format, MazeFormat},
+    maze::Maze,
+    parser::ContentParser,
+};
+
+/// Renders a report of why `source` failed to parse, for display to a user.
+///
+/// Each format that was attempted gets the offending lines of the maze, a caret under
+/// the bad columns and, where one applies, a hint. Parsing only attempts the detected
+/// format, so the other formats drawn with the same characters are attempted here too,
+/// and the report names the one that got furthest through the maze. The report ends
+/// with the format `detect_format` finds for `source` and why.
+pub fn explain(source: &str, err: &ParseMazeError) -> String {
+    let detected = detect_format(source);
+    let charset = detected.format.charset();
+    let geometry = CellGeometry::infer(source, charset);
+
+    let attempts = [MazeFormat::Small, MazeFormat::Large, MazeFormat::BoxDrawing]
+        .into_iter()
+        .filter_map(|format| {
+            let err = match err.err(format) {
+                Some(err) => err.clone(),
+                None if format.charset() == charset => {
+                    Maze::parse_as(source, format, geometry).err()?
+                }
+                None => return None,
+            };
+            Some((format, err))
+        })
+        .collect::<Vec<_>>();
+
+    let lines = source.lines().collect::<Vec<_>>();
+    let parser = ContentParser::new(source);
+    let mut out = String::new();
+
+    for (i, (attempt, err)) in attempts.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        explain_attempt(&mut out, &lines, &parser, *attempt, err);
+    }
+
+    if attempts.len() > 1 {
+        // The format that read furthest before failing is the closer match
+        let progress = |err: &SyntaxError| (err.span().line, err.span().start_col);
+        let furthest = attempts
+            .iter()
+            .map(|(_, err)| progress(err))
+            .max()
+            .expect("there should be at least two attempts");
+        let closest = attempts
+            .iter()
+            .filter(|(_, err)| progress(err) == furthest)
+            .map(|(attempt, _)| attempt.name())
+            .collect::<Vec<_>>();
+
+        if closest.len() == 1 {
+            let _ = writeln!(
+                out,
+                "\nThe {} format was the closer match, reading up to line {} before failing.",
+                closest[0], furthest.0,
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                "\nThe {} formats failed at the same place, line {}.",
+                closest.join(" and "),
+                furthest.0,
+            );
+        }
+    }
+
+    let _ = writeln!(out, "\nnote: detected the {}", detected);
+
+    out
+}
+
+fn explain_attempt(
+    out: &mut String,
+    lines: &[&str],
+    parser: &ContentParser,
+    attempt: MazeFormat,
+    err: &SyntaxError,
+) {
+    let span = err.span();
+    let gutter = span.line.to_string().len();
+
+    let _ = writeln!(out, "error: {}", err.message());
+    let _ = writeln!(
+        out,
+        "{:>gutter$}--> {} format, {}, cell {}",
+        "",
+        attempt.name(),
+        span,
+        err.cell(),
+    );
+    let _ = writeln!(out, "{:>gutter$} |", "");
+
+    // Show the line before the problem for context
+    for line in span.line.saturating_sub(1).max(1)..=span.line {
+        let text = lines.get(line - 1).copied().unwrap_or_default();
+        let _ = writeln!(out, "{:>gutter$} | {}", line, text);
+    }
+
+    let _ = writeln!(
+        out,
+        "{:>gutter$} | {}{}",
+        "",
+        " ".repeat(span.start_col - 1),
+        "^".repeat(span.end_col - span.start_col + 1),
+    );
+
+    if let Some(hint) = hint(parser, attempt, err) {
+        let _ = writeln!(out, "{:>gutter$} = hint: {}", "", hint);
+    }
+}
+
+/// Suggests a likely cause of `err`, using the surrounding source where it helps.
+fn hint(parser: &ContentParser, attempt: MazeFormat, err: &SyntaxError) -> Option<String> {
+    let charset = attempt.charset();
+    let char_at = |line, col| parser.char_at(line, col);
+    let is_corner = |c: Option<char>| c.is_some_and(|c| charset.is_corner(c));
+
+    match err {
+        SyntaxError::RaggedRow {
+            span,
+            expected,
+            found,
+            ..
+        } => Some(format!(
+            "line {} is {} {} {} than line 1",
+            span.line,
+            expected.abs_diff(*found),
+            if expected.abs_diff(*found) == 1 {
+                "character"
+            } else {
+                "characters"
+            },
+            if found < expected {
+                "shorter"
+            } else {
+                "longer"
+            },
+        )),
+        SyntaxError::TruncatedCell { span, .. } => {
+            let line_len = parser.line_len(span.line).unwrap_or(0);
+            if attempt == MazeFormat::Large
+                && span.start_col > 1
+                && is_corner(char_at(span.line, span.start_col - 1))
+                && !is_corner(char_at(span.line, span.start_col))
+            {
+                Some(String::from(
+                    "large format expects doubled `++` corners here",
+                ))
+            } else if line_len < span.end_col {
+                Some(String::from(
+                    "the line ends before this cell's right wall; check the cell widths",
+                ))
+            } else {
+                Some(String::from(
+                    "the maze ends before this row of cells does; check for missing lines",
+                ))
+            }
+        }
+        SyntaxError::UnexpectedChar {
+            span,
+            found,
+            expected,
+            ..
+        } => {
+            if attempt == MazeFormat::Small
+                && charset.is_corner(*found)
+                && (is_corner(char_at(span.line, span.start_col + 1))
+                    || is_corner(char_at(span.line, span.start_col - 1)))
+            {
+                Some(String::from(
+                    "doubled `++` corners belong to the large format, not the small one",
+                ))
+            } else if charset.is_corner(*found) {
+                Some(String::from(
+                    "a corner in the middle of a wall suggests the cells are a different size",
+                ))
+            } else if expected.starts_with("a corner") {
+                Some(format!(
+                    "corners are drawn with `{}` or left blank",
+                    charset.corner(Default::default()),
+                ))
+            } else if expected.starts_with("a wall") {
+                Some(format!(
+                    "walls are drawn with `{}` and `{}` or left blank",
+                    charset.horizontal_wall(),
+                    charset.vertical_wall(),
+                ))
+            } else {
+                Some(String::from(
+                    "a wide character straddles a wall; pad the label so it fits inside the cell",
+                ))
+            }
+        }
+        SyntaxError::InconsistentWall { side, .. } => Some(format!(
+            "the {} wall mixes wall characters and spaces; draw all of it or none of it",
+            side,
+        )),
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::{
+    cell::{Cell, CellGeometry},
+    maze::Maze,
+};
+
+/// The version of the `EdgeList` layout written by `Maze::to_edge_list`.
+pub const EDGE_LIST_VERSION: u32 = 1;
+
+/// A compact description of a maze: its dimensions, the walls between and around its cells,
+/// and the text of any cells that aren't blank.
+///
+/// Each wall is stored once, as in the small format, so a wall drawn by only one of the
+/// cells beside it becomes a wall for both. Corners are not stored; reading an edge list
+/// back draws every corner.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeList {
+    /// Always `EDGE_LIST_VERSION` when written by this crate.
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub cell_width: usize,
+    pub cell_height: usize,
+    /// Walls along the top of a cell, as `[row, col]`. Row `height` is the bottom edge of
+    /// the maze.
+    pub horizontal_walls: Vec<[usize; 2]>,
+    /// Walls along the left of a cell, as `[row, col]`. Column `width` is the right edge of
+    /// the maze.
+    pub vertical_walls: Vec<[usize; 2]>,
+    /// The inner text of every cell that isn't blank, in row-major order.
+    pub labels: Vec<EdgeLabel>,
+}
+
+/// The inner text of one cell in an `EdgeList`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeLabel {
+    pub row: usize,
+    pub col: usize,
+    pub text: String,
+}
+
+/// Why an `EdgeList` couldn't be read back into a `Maze`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EdgeListError {
+    /// The edge list was written in a layout this version of the crate doesn't know.
+    UnsupportedVersion(u32),
+    /// A wall lies outside the maze's dimensions.
+    WallOutOfBounds([usize; 2]),
+    /// A label belongs to a cell outside the maze's dimensions.
+    LabelOutOfBounds([usize; 2]),
+}
+
+impl fmt::Display for EdgeListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported edge list version {}, expected {}.",
+                version, EDGE_LIST_VERSION,
+            ),
+            Self::WallOutOfBounds([row, col]) => {
+                write!(f, "Wall at ({}, {}) is outside the maze.", row, col)
+            }
+            Self::LabelOutOfBounds([row, col]) => {
+                write!(f, "Label at ({}, {}) is outside the maze.", row, col)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EdgeListError {}
+
+impl Maze {
+    /// Describes the maze as an `EdgeList`. A wall is listed if either cell beside it has it.
+    pub fn to_edge_list(&self) -> EdgeList {
+        let geometry = self.geometry();
+        let (width, height) = (self.width(), self.height());
+        let cell = |r: usize, c: usize| self.cells.get(r).and_then(|row| row.get(c));
+
+        let mut horizontal_walls = Vec::new();
+        for r in 0..=height {
+            for c in 0..width {
+                let above = r.checked_sub(1).and_then(|r| cell(r, c));
+                if above.is_some_and(|cell| cell.wall_bottom)
+                    || cell(r, c).is_some_and(|cell| cell.wall_top)
+                {
+                    horizontal_walls.push([r, c]);
+                }
+            }
+        }
+
+        let mut vertical_walls = Vec::new();
+        for r in 0..height {
+            for c in 0..=width {
+                let left = c.checked_sub(1).and_then(|c| cell(r, c));
+                if left.is_some_and(|cell| cell.wall_right)
+                    || cell(r, c).is_some_and(|cell| cell.wall_left)
+                {
+                    vertical_walls.push([r, c]);
+                }
+            }
+        }
+
+        let labels = self
+            .iter_row_major()
+            .filter(|(_, cell)| !cell.inner_text.trim().is_empty())
+            .map(|(pos, cell)| EdgeLabel {
+                row: pos.row,
+                col: pos.col,
+                text: cell.inner_text.clone(),
+            })
+            .collect();
+
+        EdgeList {
+            version: EDGE_LIST_VERSION,
+            width,
+            height,
+            cell_width: geometry.width,
+            cell_height: geometry.height,
+            horizontal_walls,
+            vertical_walls,
+            labels,
+        }
+    }
+
+    /// Builds a maze from an `EdgeList`, with every corner drawn and blank cells filled
+    /// with spaces.
+    pub fn from_edge_list(edges: &EdgeList) -> Result<Maze, EdgeListError> {
+        if edges.version != EDGE_LIST_VERSION {
+            return Err(EdgeListError::UnsupportedVersion(edges.version));
+        }
+
+        let geometry = CellGeometry::new(edges.cell_width, edges.cell_height);
+        let blank = vec![" ".repeat(geometry.width); geometry.height].join("\n");
+        let cell = Cell {
+            corner_top_left: true,
+            corner_top_right: true,
+            corner_bottom_left: true,
+            corner_bottom_right: true,
+            inner_text: blank,
+            ..Cell::default()
+        };
+        let mut cells = vec![vec![cell; edges.width]; edges.height];
+
+        for &[r, c] in &edges.horizontal_walls {
+            if r > edges.height || c >= edges.width {
+                return Err(EdgeListError::WallOutOfBounds([r, c]));
+            }
+            if let Some(above) = r.checked_sub(1) {
+                cells[above][c].wall_bottom = true;
+            }
+            if r < edges.height {
+                cells[r][c].wall_top = true;
+            }
+        }
+
+        for &[r, c] in &edges.vertical_walls {
+            if r >= edges.height || c > edges.width {
+                return Err(EdgeListError::WallOutOfBounds([r, c]));
+            }
+            if let Some(left) = c.checked_sub(1) {
+                cells[r][left].wall_right = true;
+            }
+            if c < edges.width {
+                cells[r][c].wall_left = true;
+            }
+        }
+
+        for label in &edges.labels {
+            let cell = cells
+                .get_mut(label.row)
+                .and_then(|row| row.get_mut(label.col))
+                .ok_or(EdgeListError::LabelOutOfBounds([label.row, label.col]))?;
+            cell.inner_text = label.text.clone();
+        }
+
+        Ok(Maze { cells })
+    }
+}
@@ -0,0 +1,340 @@
+use std::fmt;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    cell::Cell,
+    maze::Maze,
+    pos::{Corner, Direction, Pos},
+};
+
+/// Why an edit to a maze was refused. The maze is left unchanged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EditError {
+    /// The cell is outside the maze.
+    OutOfBounds(Pos),
+    /// There is no cell in `direction` to carve a passage to.
+    NoNeighbour { pos: Pos, direction: Direction },
+    /// The label is wider or has more lines than the cell.
+    LabelDoesNotFit { pos: Pos, label: String },
+    /// A row can't be inserted or removed at `index` in a maze `height` rows tall.
+    RowOutOfBounds { index: usize, height: usize },
+    /// A column can't be inserted or removed at `index` in a maze `width` columns wide.
+    ColOutOfBounds { index: usize, width: usize },
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds(pos) => write!(f, "Cell {} is outside the maze.", pos),
+            Self::NoNeighbour { pos, direction } => write!(
+                f,
+                "Cell {} has no neighbour on its {} side to carve a passage to.",
+                pos, direction,
+            ),
+            Self::LabelDoesNotFit { pos, label } => {
+                write!(f, "Label {:?} does not fit in cell {}.", label, pos)
+            }
+            Self::RowOutOfBounds { index, height } => write!(
+                f,
+                "Row {} is out of bounds for a maze {} rows tall.",
+                index, height,
+            ),
+            Self::ColOutOfBounds { index, width } => write!(
+                f,
+                "Column {} is out of bounds for a maze {} columns wide.",
+                index, width,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+/// How to close the gap left by a removed cell: the wall on `side` of `target`, and the
+/// corners at its ends.
+struct Join {
+    target: Pos,
+    side: Direction,
+    wall: bool,
+    corners: [bool; 2],
+}
+
+impl Maze {
+    /// Adds or removes the wall on `side` of the cell at `pos`, along with the facing wall of
+    /// its neighbour. Adding a wall also adds the corners at its ends, to every cell that
+    /// shares them. Removing one leaves the corners, which the formats draw either way.
+    pub fn set_wall(&mut self, pos: Pos, side: Direction, present: bool) -> Result<(), EditError> {
+        if self.get(pos).is_none() {
+            return Err(EditError::OutOfBounds(pos));
+        }
+        self.link(pos, side, present);
+        Ok(())
+    }
+
+    /// Opens a passage from the cell at `pos` to its neighbour in `direction`. Use
+    /// `set_wall` to open the outer wall of the maze.
+    pub fn carve(&mut self, pos: Pos, direction: Direction) -> Result<(), EditError> {
+        if self.get(pos).is_none() {
+            return Err(EditError::OutOfBounds(pos));
+        }
+        if pos
+            .step(direction)
+            .and_then(|next| self.get(next))
+            .is_none()
+        {
+            return Err(EditError::NoNeighbour { pos, direction });
+        }
+        self.link(pos, direction, false);
+        Ok(())
+    }
+
+    /// Replaces the inner text of the cell at `pos` with `label`, centred in the cell. An
+    /// empty label blanks the cell, and a label with several lines needs a cell as tall.
+    pub fn set_label(&mut self, pos: Pos, label: &str) -> Result<(), EditError> {
+        let cell = self.get_mut(pos).ok_or(EditError::OutOfBounds(pos))?;
+        let geometry = cell.geometry();
+        let lines = match label {
+            "" => Vec::new(),
+            label => label.split('\n').collect::<Vec<_>>(),
+        };
+
+        if lines.len() > geometry.height || lines.iter().any(|line| line.width() > geometry.width) {
+            return Err(EditError::LabelDoesNotFit {
+                pos,
+                label: String::from(label),
+            });
+        }
+
+        let top = (geometry.height - lines.len()) / 2;
+        cell.inner_text = (0..geometry.height)
+            .map(
+                |line| match line.checked_sub(top).and_then(|i| lines.get(i)) {
+                    Some(text) => {
+                        let left = (geometry.width - text.width()) / 2;
+                        let right = geometry.width - text.width() - left;
+                        format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+                    }
+                    None => " ".repeat(geometry.width),
+                },
+            )
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(())
+    }
+
+    /// Inserts a row of blank cells before row `index`, or at the bottom if `index` is the
+    /// height. The new cells are walled off from each other, and a passage that crossed the
+    /// gap is stretched through the new cell, so the existing cells stay connected as they were.
+    pub fn insert_row(&mut self, index: usize) -> Result<(), EditError> {
+        let height = self.height();
+        if index > height {
+            return Err(EditError::RowOutOfBounds { index, height });
+        }
+
+        let open = (0..self.width())
+            .map(|c| index > 0 && self.can_move(Pos::new(index - 1, c), Direction::Down))
+            .collect::<Vec<_>>();
+        let blank = self.blank_cell();
+        self.cells.insert(index, vec![blank; open.len()]);
+
+        for (c, open) in open.into_iter().enumerate() {
+            self.stitch(Pos::new(index, c), Direction::Down, open);
+        }
+        Ok(())
+    }
+
+    /// Inserts a column of blank cells before column `index`, or at the right if `index` is
+    /// the width, as `insert_row` does for rows. Rows too short to reach `index` are skipped.
+    pub fn insert_col(&mut self, index: usize) -> Result<(), EditError> {
+        let width = self.width();
+        if index > width {
+            return Err(EditError::ColOutOfBounds { index, width });
+        }
+
+        let open = (0..self.height())
+            .map(|r| {
+                (index <= self.cells[r].len())
+                    .then(|| index > 0 && self.can_move(Pos::new(r, index - 1), Direction::Right))
+            })
+            .collect::<Vec<_>>();
+        let blank = self.blank_cell();
+
+        for (r, open) in open.iter().enumerate() {
+            if open.is_some() {
+                self.cells[r].insert(index, blank.clone());
+            }
+        }
+        for (r, open) in open.into_iter().enumerate() {
+            if let Some(open) = open {
+                self.stitch(Pos::new(r, index), Direction::Right, open);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes row `index`. The cells either side of it become neighbours, with a passage
+    /// between them where one ran through the removed cell. Removing the top or bottom row
+    /// moves its outer walls to the row that takes its place.
+    pub fn remove_row(&mut self, index: usize) -> Result<(), EditError> {
+        let height = self.height();
+        if index >= height {
+            return Err(EditError::RowOutOfBounds { index, height });
+        }
+
+        let joins = (0..self.cells[index].len())
+            .filter_map(|c| self.join(Pos::new(index, c), Direction::Down))
+            .collect::<Vec<_>>();
+        self.cells.remove(index);
+        for join in joins {
+            self.apply(join);
+        }
+        Ok(())
+    }
+
+    /// Removes column `index`, as `remove_row` does for rows. Rows too short to reach
+    /// `index` are left alone.
+    pub fn remove_col(&mut self, index: usize) -> Result<(), EditError> {
+        let width = self.width();
+        if index >= width {
+            return Err(EditError::ColOutOfBounds { index, width });
+        }
+
+        let joins = (0..self.height())
+            .filter(|&r| index < self.cells[r].len())
+            .filter_map(|r| self.join(Pos::new(r, index), Direction::Right))
+            .collect::<Vec<_>>();
+        for row in &mut self.cells {
+            if index < row.len() {
+                row.remove(index);
+            }
+        }
+        for join in joins {
+            self.apply(join);
+        }
+        Ok(())
+    }
+
+    /// Sets the wall on `side` of `pos` and the facing wall of its neighbour, adding the
+    /// corners at its ends if the wall is present.
+    fn link(&mut self, pos: Pos, side: Direction, present: bool) {
+        if let Some(cell) = self.get_mut(pos) {
+            cell.set_wall(side, present);
+        }
+        if let Some(neighbour) = pos.step(side).and_then(|next| self.get_mut(next)) {
+            neighbour.set_wall(side.opposite(), present);
+        }
+        if present {
+            for corner in wall_ends(side) {
+                self.add_corner_point(pos, corner);
+            }
+        }
+    }
+
+    /// Adds `corner` of the cell at `pos` to every cell that shares the point.
+    fn add_corner_point(&mut self, pos: Pos, corner: Corner) {
+        let (r, c) = match corner {
+            Corner::TopLeft => (pos.row, pos.col),
+            Corner::TopRight => (pos.row, pos.col + 1),
+            Corner::BottomLeft => (pos.row + 1, pos.col),
+            Corner::BottomRight => (pos.row + 1, pos.col + 1),
+        };
+        let sharing = [
+            (r.checked_sub(1), c.checked_sub(1), Corner::BottomRight),
+            (r.checked_sub(1), Some(c), Corner::BottomLeft),
+            (Some(r), c.checked_sub(1), Corner::TopRight),
+            (Some(r), Some(c), Corner::TopLeft),
+        ];
+        for (r, c, corner) in sharing {
+            if let (Some(r), Some(c)) = (r, c) {
+                if let Some(cell) = self.get_mut(Pos::new(r, c)) {
+                    cell.set_corner(corner, true);
+                }
+            }
+        }
+    }
+
+    /// Walls in a newly inserted cell on every side, except across the insertion (along
+    /// `forward` and back) when `open`, and gives it all four corners.
+    fn stitch(&mut self, pos: Pos, forward: Direction, open: bool) {
+        for side in Direction::ALL {
+            let across = side == forward || side == forward.opposite();
+            self.link(pos, side, !(open && across));
+        }
+        for corner in [
+            Corner::TopLeft,
+            Corner::TopRight,
+            Corner::BottomLeft,
+            Corner::BottomRight,
+        ] {
+            self.add_corner_point(pos, corner);
+        }
+    }
+
+    /// Works out how to close the gap left by removing the cell at `pos` from a row (with
+    /// `forward` `Down`) or a column (`Right`). The target is given as it will be after the
+    /// removal.
+    fn join(&self, pos: Pos, forward: Direction) -> Option<Join> {
+        let back = forward.opposite();
+        let removed = self.get(pos)?;
+        let before = pos.step(back).filter(|&p| self.get(p).is_some());
+        let after = pos.step(forward).filter(|&p| self.get(p).is_some());
+
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                let (first, second) = (&self[before], &self[after]);
+                let [a, b] = wall_ends(forward);
+                let [c, d] = wall_ends(back);
+                Some(Join {
+                    target: before,
+                    side: forward,
+                    wall: !(self.can_move(before, forward) && self.can_move(pos, forward)),
+                    corners: [
+                        first.has_corner(a) || second.has_corner(c),
+                        first.has_corner(b) || second.has_corner(d),
+                    ],
+                })
+            }
+            (Some(before), None) => Some(Join {
+                target: before,
+                side: forward,
+                wall: removed.has_wall(forward),
+                corners: wall_ends(forward).map(|corner| removed.has_corner(corner)),
+            }),
+            (None, Some(_)) => Some(Join {
+                target: pos,
+                side: back,
+                wall: removed.has_wall(back),
+                corners: wall_ends(back).map(|corner| removed.has_corner(corner)),
+            }),
+            (None, None) => None,
+        }
+    }
+
+    fn apply(&mut self, join: Join) {
+        self.link(join.target, join.side, join.wall);
+        for (corner, present) in wall_ends(join.side).into_iter().zip(join.corners) {
+            if present {
+                self.add_corner_point(join.target, corner);
+            }
+        }
+    }
+
+    fn blank_cell(&self) -> Cell {
+        let geometry = self.geometry();
+        Cell {
+            inner_text: vec![" ".repeat(geometry.width); geometry.height].join("\n"),
+            ..Cell::default()
+        }
+    }
+}
+
+/// Returns the corners at the ends of the wall on `side`, top or left first.
+fn wall_ends(side: Direction) -> [Corner; 2] {
+    match side {
+        Direction::Up => [Corner::TopLeft, Corner::TopRight],
+        Direction::Right => [Corner::TopRight, Corner::BottomRight],
+        Direction::Down => [Corner::BottomLeft, Corner::BottomRight],
+        Direction::Left => [Corner::TopLeft, Corner::BottomLeft],
+    }
+}
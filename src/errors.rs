@@ -1,10 +1,198 @@
 use std::fmt;
 
-use anyhow::Error;
+use crate::{
+    format::MazeFormat,
+    pos::{Direction, Pos},
+};
+
+/// A span of columns on a single line of maze source.
+///
+/// Lines and columns are 1-based and columns are measured in display width, matching
+/// `ContentParser`. `end_col` is inclusive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub start_col: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, start_col: usize, end_col: usize) -> Self {
+        Self {
+            line,
+            start_col,
+            end_col,
+        }
+    }
+
+    /// Returns a span covering the single column `col`.
+    pub fn at(line: usize, col: usize) -> Self {
+        Self::new(line, col, col)
+    }
+
+    /// Moves the span so that line 1, column 1 lands on `(line, col)`.
+    fn offset_by(self, (line, col): (usize, usize)) -> Self {
+        Self::new(
+            self.line + line - 1,
+            self.start_col + col - 1,
+            self.end_col + col - 1,
+        )
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start_col == self.end_col {
+            write!(f, "line {}, column {}", self.line, self.start_col)
+        } else {
+            write!(
+                f,
+                "line {}, columns {}-{}",
+                self.line, self.start_col, self.end_col,
+            )
+        }
+    }
+}
+
+/// Why a maze could not be parsed in one particular format.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SyntaxError {
+    /// A line's width differs from the width of the first line.
+    RaggedRow {
+        span: Span,
+        cell: Pos,
+        expected: usize,
+        found: usize,
+    },
+    /// A cell runs past the end of its line or past the last line.
+    TruncatedCell { span: Span, cell: Pos },
+    /// A character that cannot appear at this position of a cell.
+    UnexpectedChar {
+        span: Span,
+        cell: Pos,
+        found: char,
+        expected: &'static str,
+    },
+    /// A wall that is only partly drawn, such as `+- -+`.
+    InconsistentWall {
+        span: Span,
+        cell: Pos,
+        side: Direction,
+    },
+}
+
+impl SyntaxError {
+    /// Returns the location of the problem in the maze source.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::RaggedRow { span, .. }
+            | Self::TruncatedCell { span, .. }
+            | Self::UnexpectedChar { span, .. }
+            | Self::InconsistentWall { span, .. } => *span,
+        }
+    }
+
+    /// Returns the coordinate of the cell the problem belongs to.
+    pub fn cell(&self) -> Pos {
+        match self {
+            Self::RaggedRow { cell, .. }
+            | Self::TruncatedCell { cell, .. }
+            | Self::UnexpectedChar { cell, .. }
+            | Self::InconsistentWall { cell, .. } => *cell,
+        }
+    }
+
+    /// Returns a description of the problem, without its location.
+    pub fn message(&self) -> String {
+        match self {
+            Self::RaggedRow {
+                expected, found, ..
+            } => format!(
+                "Ragged row: Line is {} columns wide, expected {}.",
+                found, expected,
+            ),
+            Self::TruncatedCell { .. } => String::from("Truncated cell: Cell is cut off."),
+            Self::UnexpectedChar {
+                found, expected, ..
+            } => format!(
+                "Unexpected character: Found {:?}, expected {}.",
+                found, expected,
+            ),
+            Self::InconsistentWall { side, .. } => {
+                format!("Inconsistent wall: The {} wall is only partly drawn.", side,)
+            }
+        }
+    }
+
+    /// Rebases an error reported by a lone cell onto the cell's place in the maze,
+    /// where the cell's top left corner sits at `origin`.
+    pub(crate) fn in_maze(mut self, origin: (usize, usize), pos: Pos) -> Self {
+        match &mut self {
+            Self::RaggedRow { span, cell, .. }
+            | Self::TruncatedCell { span, cell }
+            | Self::UnexpectedChar { span, cell, .. }
+            | Self::InconsistentWall { span, cell, .. } => {
+                *span = span.offset_by(origin);
+                *cell = pos;
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (cell {}): {}",
+            self.span(),
+            self.cell(),
+            self.message()
+        )
+    }
+}
+
+impl std::error::Error for SyntaxError {}
 
 pub struct ParseMazeError {
-    pub sm_err: Option<Error>,
-    pub lg_err: Option<Error>,
+    pub sm_err: Option<SyntaxError>,
+    pub lg_err: Option<SyntaxError>,
+    pub box_err: Option<SyntaxError>,
+}
+
+impl ParseMazeError {
+    /// Records `err` as the failure of the given format.
+    pub(crate) fn new(format: MazeFormat, err: SyntaxError) -> Self {
+        let mut this = Self {
+            sm_err: None,
+            lg_err: None,
+            box_err: None,
+        };
+        *this.err_mut(format) = Some(err);
+        this
+    }
+
+    /// Returns the failure recorded for the given format, if it was attempted.
+    pub fn err(&self, format: MazeFormat) -> Option<&SyntaxError> {
+        match format {
+            MazeFormat::Small => self.sm_err.as_ref(),
+            MazeFormat::Large => self.lg_err.as_ref(),
+            MazeFormat::BoxDrawing => self.box_err.as_ref(),
+        }
+    }
+
+    fn err_mut(&mut self, format: MazeFormat) -> &mut Option<SyntaxError> {
+        match format {
+            MazeFormat::Small => &mut self.sm_err,
+            MazeFormat::Large => &mut self.lg_err,
+            MazeFormat::BoxDrawing => &mut self.box_err,
+        }
+    }
+
+    /// Renders a report of the failure with snippets of `source`, as `explain` does.
+    pub fn explain(&self, source: &str) -> String {
+        crate::diagnostic::explain(source, self)
+    }
 }
 
 impl fmt::Display for ParseMazeError {
@@ -16,6 +204,9 @@ impl fmt::Display for ParseMazeError {
         if let Some(lg_err) = &self.lg_err {
             write!(f, " Large maze error: {}", lg_err)?;
         }
+        if let Some(box_err) = &self.box_err {
+            write!(f, " Box-drawing maze error: {}", box_err)?;
+        }
         Ok(())
     }
 }
@@ -25,6 +216,7 @@ impl fmt::Debug for ParseMazeError {
         f.debug_struct("ParseMazeError")
             .field("sm_err", &self.sm_err)
             .field("lg_err", &self.lg_err)
+            .field("box_err", &self.box_err)
             .finish()
     }
 }
@@ -0,0 +1,230 @@
+use std::fmt;
+
+use crate::{charset::Charset, parser::ContentParser};
+
+/// The layouts a maze can be drawn in.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MazeFormat {
+    /// Neighbouring cells share walls and corners: `+---+---+`.
+    #[default]
+    Small,
+    /// Every cell draws all of its own walls, doubling them between cells: `+---++---+`.
+    Large,
+    /// Laid out like the small format, drawn with box-drawing characters: `┌───┬───┐`.
+    BoxDrawing,
+}
+
+impl MazeFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Small => "small",
+            Self::Large => "large",
+            Self::BoxDrawing => "box-drawing",
+        }
+    }
+
+    /// Returns the characters the format is drawn with.
+    pub fn charset(self) -> Charset {
+        match self {
+            Self::Small | Self::Large => Charset::Ascii,
+            Self::BoxDrawing => Charset::BoxDrawing,
+        }
+    }
+
+    /// Returns the number of extra columns and lines between neighbouring cells.
+    pub(crate) fn offset(self) -> isize {
+        match self {
+            Self::Small | Self::BoxDrawing => 0,
+            Self::Large => 1,
+        }
+    }
+}
+
+impl fmt::Display for MazeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An observation about a maze's source that points towards one format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Evidence {
+    /// Box-drawing characters such as `┼` or `─`.
+    BoxDrawingChars(usize),
+    /// Corners directly next to each other, `++`, as between large format cells.
+    DoubledCorners(usize),
+    /// Vertical walls directly next to each other, `||`, as between large format cells.
+    DoubledWalls(usize),
+    /// Lines of corners directly above each other, as between large format rows.
+    DoubledBoundaryLines(usize),
+    /// Lone corners inside a line, shared by two small format cells.
+    SharedCorners(usize),
+    /// Lines of corners between two lines of cell interiors, as between small format rows.
+    SharedBoundaryLines(usize),
+}
+
+impl Evidence {
+    /// Returns the format this evidence points towards.
+    pub fn format(&self) -> MazeFormat {
+        match self {
+            Self::BoxDrawingChars(_) => MazeFormat::BoxDrawing,
+            Self::DoubledCorners(_) | Self::DoubledWalls(_) | Self::DoubledBoundaryLines(_) => {
+                MazeFormat::Large
+            }
+            Self::SharedCorners(_) | Self::SharedBoundaryLines(_) => MazeFormat::Small,
+        }
+    }
+
+    /// Returns how many times this evidence was observed.
+    pub fn count(&self) -> usize {
+        match self {
+            Self::BoxDrawingChars(n)
+            | Self::DoubledCorners(n)
+            | Self::DoubledWalls(n)
+            | Self::DoubledBoundaryLines(n)
+            | Self::SharedCorners(n)
+            | Self::SharedBoundaryLines(n) => *n,
+        }
+    }
+}
+
+impl fmt::Display for Evidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.count();
+        let (one, many) = match self {
+            Self::BoxDrawingChars(_) => ("box-drawing character", "box-drawing characters"),
+            Self::DoubledCorners(_) => ("doubled `++` corner", "doubled `++` corners"),
+            Self::DoubledWalls(_) => ("doubled `||` wall", "doubled `||` walls"),
+            Self::DoubledBoundaryLines(_) => {
+                ("doubled line of corners", "doubled lines of corners")
+            }
+            Self::SharedCorners(_) => ("shared corner", "shared corners"),
+            Self::SharedBoundaryLines(_) => ("shared line of corners", "shared lines of corners"),
+        };
+        write!(f, "{} {}", n, if n == 1 { one } else { many })
+    }
+}
+
+/// The result of `detect_format`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatDetection {
+    pub format: MazeFormat,
+    /// How strongly the evidence favours `format`, from 0.5 (a coin toss) to 1.0.
+    pub confidence: f64,
+    /// Everything that was observed, including evidence for other formats.
+    pub evidence: Vec<Evidence>,
+}
+
+impl fmt::Display for FormatDetection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} format ({:.0}% confidence",
+            self.format,
+            self.confidence * 100.0,
+        )?;
+        for (i, evidence) in self.evidence.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { ": " } else { ", " }, evidence)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Works out which format the maze in `s` is drawn in.
+///
+/// Box-drawing characters decide the box-drawing format. Otherwise the small and large
+/// formats are told apart by their corners: the large format doubles them between
+/// cells (`++`, and a line of corners directly below another), while the small format
+/// shares a single corner (`-+-`, and a line of corners between two cell interiors).
+/// Without any evidence either way, as for a single cell, the small format is chosen.
+pub fn detect_format(s: &str) -> FormatDetection {
+    let box_chars = s
+        .chars()
+        .filter(|&c| c != '·' && Charset::BoxDrawing.is_corner(c))
+        .count();
+    let ascii_corners = s.chars().filter(|&c| Charset::Ascii.is_corner(c)).count();
+
+    if box_chars > 0 {
+        return FormatDetection {
+            format: MazeFormat::BoxDrawing,
+            confidence: box_chars as f64 / (box_chars + ascii_corners) as f64,
+            evidence: vec![Evidence::BoxDrawingChars(box_chars)],
+        };
+    }
+
+    let charset = Charset::Ascii;
+    let parser = ContentParser::new(s);
+    let char_at = |line, col| parser.char_at(line, col);
+    let is_corner = |c: Option<char>| c.is_some_and(|c| charset.is_corner(c));
+    let is_vertical = |c: Option<char>| c.is_some_and(|c| charset.is_vertical_wall(c));
+
+    let mut doubled_corners = 0;
+    let mut doubled_walls = 0;
+    let mut shared_corners = 0;
+
+    let line_count = parser.line_count();
+    let mut is_boundary = vec![false; line_count];
+
+    for line in 1..=line_count {
+        let len = parser.line_len(line).unwrap_or(0);
+        for col in 1..=len {
+            let c = char_at(line, col);
+            let next = char_at(line, col + 1);
+
+            if is_corner(c) {
+                is_boundary[line - 1] = true;
+                if is_corner(next) {
+                    doubled_corners += 1;
+                } else if col > 1 && col < len && !is_corner(char_at(line, col - 1)) {
+                    shared_corners += 1;
+                }
+            } else if is_vertical(c) && is_vertical(next) {
+                doubled_walls += 1;
+            }
+        }
+    }
+
+    let doubled_lines = is_boundary
+        .windows(2)
+        .filter(|pair| pair[0] && pair[1])
+        .count();
+    let shared_lines = is_boundary
+        .windows(3)
+        .filter(|triple| !triple[0] && triple[1] && !triple[2])
+        .count();
+
+    let evidence = [
+        Evidence::DoubledCorners(doubled_corners),
+        Evidence::DoubledWalls(doubled_walls),
+        Evidence::DoubledBoundaryLines(doubled_lines),
+        Evidence::SharedCorners(shared_corners),
+        Evidence::SharedBoundaryLines(shared_lines),
+    ]
+    .into_iter()
+    .filter(|evidence| evidence.count() > 0)
+    .collect::<Vec<_>>();
+
+    let score = |format| {
+        evidence
+            .iter()
+            .filter(|evidence| evidence.format() == format)
+            .map(Evidence::count)
+            .sum::<usize>()
+    };
+    let small = score(MazeFormat::Small);
+    let large = score(MazeFormat::Large);
+
+    let (format, confidence) = if small + large == 0 {
+        (MazeFormat::Small, 0.5)
+    } else if large > small {
+        (MazeFormat::Large, large as f64 / (small + large) as f64)
+    } else {
+        (MazeFormat::Small, small as f64 / (small + large) as f64)
+    };
+
+    FormatDetection {
+        format,
+        confidence,
+        evidence,
+    }
+}
@@ -0,0 +1,375 @@
+use std::fmt;
+
+use crate::{
+    cell::{Cell, CellGeometry},
+    maze::Maze,
+    pos::{Direction, Pos},
+};
+
+/// An algorithm for generating perfect mazes, where every cell is reachable from every
+/// other by exactly one route.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Generator {
+    /// Depth-first search with backtracking. Long, winding corridors.
+    #[default]
+    RecursiveBacktracker,
+    /// Randomized Kruskal's algorithm, joining random walls between unconnected regions.
+    Kruskal,
+    /// Randomized Prim's algorithm, growing from one cell. Many short dead ends.
+    Prim,
+    /// Wilson's algorithm, built from loop-erased random walks. Unbiased.
+    Wilson,
+    /// Eller's algorithm, generating one row at a time.
+    Eller,
+    /// Opening each cell up or left at random. Open along the top row and left column.
+    BinaryTree,
+}
+
+impl Generator {
+    /// All generators, in the order they are documented.
+    pub const ALL: [Generator; 6] = [
+        Self::RecursiveBacktracker,
+        Self::Kruskal,
+        Self::Prim,
+        Self::Wilson,
+        Self::Eller,
+        Self::BinaryTree,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::RecursiveBacktracker => "backtracker",
+            Self::Kruskal => "kruskal",
+            Self::Prim => "prim",
+            Self::Wilson => "wilson",
+            Self::Eller => "eller",
+            Self::BinaryTree => "binary-tree",
+        }
+    }
+
+    /// Returns the generator with the given `name`, as returned by `Generator::name`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|generator| generator.name() == name)
+    }
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Maze {
+    /// Generates a perfect maze `width` cells wide and `height` cells tall.
+    ///
+    /// The same `seed` always produces the same maze. Every corner is drawn and neighbouring
+    /// cells agree on every wall, so `render_sm` gives text that parses back to an equal maze.
+    pub fn generate(width: usize, height: usize, generator: Generator, seed: u64) -> Maze {
+        let mut grid = Grid::new(width, height, seed);
+        if width > 0 && height > 0 {
+            match generator {
+                Generator::RecursiveBacktracker => grid.recursive_backtracker(),
+                Generator::Kruskal => grid.kruskal(),
+                Generator::Prim => grid.prim(),
+                Generator::Wilson => grid.wilson(),
+                Generator::Eller => grid.eller(),
+                Generator::BinaryTree => grid.binary_tree(),
+            }
+        }
+        grid.maze
+    }
+}
+
+/// A small, fast generator (SplitMix64), kept here so seeds give the same mazes on
+/// every platform and in every version of our dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`, which must not be empty.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn coin(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A maze being carved out of a grid that starts with every wall in place.
+struct Grid {
+    maze: Maze,
+    width: usize,
+    height: usize,
+    rng: Rng,
+}
+
+impl Grid {
+    fn new(width: usize, height: usize, seed: u64) -> Self {
+        let geometry = CellGeometry::default();
+        let cell = Cell {
+            wall_top: true,
+            wall_bottom: true,
+            wall_left: true,
+            wall_right: true,
+            corner_top_left: true,
+            corner_top_right: true,
+            corner_bottom_left: true,
+            corner_bottom_right: true,
+            inner_text: " ".repeat(geometry.width),
+        };
+
+        let cells = if width == 0 {
+            Vec::new()
+        } else {
+            vec![vec![cell; width]; height]
+        };
+
+        Self {
+            maze: Maze { cells },
+            width,
+            height,
+            rng: Rng(seed),
+        }
+    }
+
+    fn index(&self, pos: Pos) -> usize {
+        pos.row * self.width + pos.col
+    }
+
+    fn pos(&self, index: usize) -> Pos {
+        Pos::new(index / self.width, index % self.width)
+    }
+
+    fn random_pos(&mut self) -> Pos {
+        let index = self.rng.below(self.width * self.height);
+        self.pos(index)
+    }
+
+    /// Returns the neighbours of `pos` inside the grid, walls or not.
+    fn neighbours(&self, pos: Pos) -> Vec<(Direction, Pos)> {
+        self.maze.neighbors(pos).collect()
+    }
+
+    /// Removes the wall between `pos` and its neighbour in `direction`, on both sides.
+    fn carve(&mut self, pos: Pos, direction: Direction) {
+        let next = pos
+            .step(direction)
+            .expect("carved walls are between two cells");
+        self.maze.cells[pos.row][pos.col].set_wall(direction, false);
+        self.maze.cells[next.row][next.col].set_wall(direction.opposite(), false);
+    }
+
+    fn recursive_backtracker(&mut self) {
+        let mut visited = vec![false; self.width * self.height];
+        let start = self.random_pos();
+        visited[self.index(start)] = true;
+
+        // An explicit stack, since large mazes would overflow the call stack
+        let mut stack = vec![start];
+        while let Some(&pos) = stack.last() {
+            let unvisited = self
+                .neighbours(pos)
+                .into_iter()
+                .filter(|&(_, next)| !visited[self.index(next)])
+                .collect::<Vec<_>>();
+
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+
+            let (direction, next) = unvisited[self.rng.below(unvisited.len())];
+            self.carve(pos, direction);
+            visited[self.index(next)] = true;
+            stack.push(next);
+        }
+    }
+
+    fn kruskal(&mut self) {
+        let mut walls = Vec::new();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let pos = Pos::new(row, col);
+                if col + 1 < self.width {
+                    walls.push((pos, Direction::Right));
+                }
+                if row + 1 < self.height {
+                    walls.push((pos, Direction::Down));
+                }
+            }
+        }
+        self.rng.shuffle(&mut walls);
+
+        let mut sets = DisjointSets::new(self.width * self.height);
+        for (pos, direction) in walls {
+            let next = pos.step(direction).expect("walls are between two cells");
+            if sets.union(self.index(pos), self.index(next)) {
+                self.carve(pos, direction);
+            }
+        }
+    }
+
+    fn prim(&mut self) {
+        let mut visited = vec![false; self.width * self.height];
+        let start = self.random_pos();
+        visited[self.index(start)] = true;
+
+        let mut frontier = self
+            .neighbours(start)
+            .into_iter()
+            .map(|(direction, _)| (start, direction))
+            .collect::<Vec<_>>();
+
+        while !frontier.is_empty() {
+            let (pos, direction) = frontier.swap_remove(self.rng.below(frontier.len()));
+            let next = pos
+                .step(direction)
+                .expect("frontier walls are between two cells");
+            if visited[self.index(next)] {
+                continue;
+            }
+
+            self.carve(pos, direction);
+            visited[self.index(next)] = true;
+            for (direction, beyond) in self.neighbours(next) {
+                if !visited[self.index(beyond)] {
+                    frontier.push((next, direction));
+                }
+            }
+        }
+    }
+
+    fn wilson(&mut self) {
+        let cells = self.width * self.height;
+        let mut in_maze = vec![false; cells];
+        let first = self.random_pos();
+        in_maze[self.index(first)] = true;
+
+        // The last direction the walk left each cell by. Revisiting a cell overwrites it,
+        // which erases the loop the walk just made.
+        let mut exits = vec![Direction::Up; cells];
+
+        for index in 0..cells {
+            if in_maze[index] {
+                continue;
+            }
+
+            let start = self.pos(index);
+            let mut pos = start;
+            while !in_maze[self.index(pos)] {
+                let neighbours = self.neighbours(pos);
+                let (direction, next) = neighbours[self.rng.below(neighbours.len())];
+                exits[self.index(pos)] = direction;
+                pos = next;
+            }
+
+            let mut pos = start;
+            while !in_maze[self.index(pos)] {
+                let direction = exits[self.index(pos)];
+                self.carve(pos, direction);
+                in_maze[self.index(pos)] = true;
+                pos = pos.step(direction).expect("the walk stays inside the grid");
+            }
+        }
+    }
+
+    fn eller(&mut self) {
+        let mut sets = DisjointSets::new(self.width * self.height);
+
+        for row in 0..self.height {
+            let last_row = row + 1 == self.height;
+
+            // Join neighbours in different sets, always on the last row so it ends connected
+            for col in 0..self.width - 1 {
+                let (pos, next) = (Pos::new(row, col), Pos::new(row, col + 1));
+                if (last_row || self.rng.coin()) && sets.union(self.index(pos), self.index(next)) {
+                    self.carve(pos, Direction::Right);
+                }
+            }
+            if last_row {
+                break;
+            }
+
+            // Every set needs at least one way down, or it would be cut off
+            let mut cols = (0..self.width).collect::<Vec<_>>();
+            self.rng.shuffle(&mut cols);
+            let mut has_way_down = vec![false; self.width * self.height];
+            for (i, &col) in cols.iter().enumerate() {
+                let pos = Pos::new(row, col);
+                let set = sets.find(self.index(pos));
+                let is_last_of_set = cols[i + 1..]
+                    .iter()
+                    .all(|&other| sets.find(self.index(Pos::new(row, other))) != set);
+
+                if !has_way_down[set] && is_last_of_set || self.rng.coin() {
+                    self.carve(pos, Direction::Down);
+                    sets.union(self.index(pos), self.index(Pos::new(row + 1, col)));
+                    has_way_down[set] = true;
+                }
+            }
+        }
+    }
+
+    fn binary_tree(&mut self) {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let pos = Pos::new(row, col);
+                let direction = match (row, col) {
+                    (0, 0) => continue,
+                    (0, _) => Direction::Left,
+                    (_, 0) => Direction::Up,
+                    _ if self.rng.coin() => Direction::Up,
+                    _ => Direction::Left,
+                };
+                self.carve(pos, direction);
+            }
+        }
+    }
+}
+
+/// Union-find over cell indices, used to tell whether two cells are already connected.
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+        i
+    }
+
+    /// Joins the sets of `a` and `b`, returning `false` if they were already one set.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        self.parents[b] = a;
+        true
+    }
+}
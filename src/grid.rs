@@ -0,0 +1,92 @@
+use std::ops::Index;
+
+use crate::{
+    cell::Cell,
+    maze::Maze,
+    pos::{Direction, Pos},
+};
+
+impl Maze {
+    /// Returns the number of columns, taken from the longest row.
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the cell at `pos`, or `None` if it is outside the maze.
+    pub fn get(&self, pos: Pos) -> Option<&Cell> {
+        self.cells.get(pos.row)?.get(pos.col)
+    }
+
+    /// Returns the cell at `pos` for editing, or `None` if it is outside the maze.
+    ///
+    /// Changes made through it touch this cell alone; the editing methods such as
+    /// `Maze::set_wall` keep neighbouring cells in step.
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut Cell> {
+        self.cells.get_mut(pos.row)?.get_mut(pos.col)
+    }
+
+    /// Returns the cells next to `pos` that are inside the maze, walls or not, along with
+    /// the direction of each.
+    pub fn neighbors(&self, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+        Direction::ALL.into_iter().filter_map(move |direction| {
+            let next = pos.step(direction)?;
+            self.get(next).map(|_| (direction, next))
+        })
+    }
+
+    /// Returns whether there is an open passage from `pos` to its neighbour in `direction`.
+    ///
+    /// Both cells must exist, and neither may have a wall on the side they share. This
+    /// matters for mazes parsed from the large format, where the two sides can disagree.
+    pub fn can_move(&self, pos: Pos, direction: Direction) -> bool {
+        let Some(next) = pos.step(direction) else {
+            return false;
+        };
+        match (self.get(pos), self.get(next)) {
+            (Some(cell), Some(neighbour)) => {
+                !cell.has_wall(direction) && !neighbour.has_wall(direction.opposite())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the neighbours of `pos` that can be moved to, along with the direction of each.
+    pub fn open_neighbors(&self, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+        self.neighbors(pos)
+            .filter(move |&(direction, _)| self.can_move(pos, direction))
+    }
+
+    /// Iterates over the cells a row at a time, from the top left.
+    pub fn iter_row_major(&self) -> impl Iterator<Item = (Pos, &Cell)> + '_ {
+        self.cells.iter().enumerate().flat_map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(c, cell)| (Pos::new(r, c), cell))
+        })
+    }
+
+    /// Iterates over the cells a column at a time, from the top left. Rows shorter than
+    /// the maze is wide are skipped in the columns they lack.
+    pub fn iter_col_major(&self) -> impl Iterator<Item = (Pos, &Cell)> + '_ {
+        (0..self.width()).flat_map(move |c| {
+            (0..self.height()).filter_map(move |r| {
+                let pos = Pos::new(r, c);
+                self.get(pos).map(|cell| (pos, cell))
+            })
+        })
+    }
+}
+
+impl Index<Pos> for Maze {
+    type Output = Cell;
+
+    /// Returns the cell at `pos`, panicking if it is outside the maze.
+    fn index(&self, pos: Pos) -> &Cell {
+        &self.cells[pos.row][pos.col]
+    }
+}
@@ -0,0 +1,318 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::{
+    cell::Cell,
+    edit::EditError,
+    maze::Maze,
+    pos::{Direction, Pos},
+};
+
+/// One reversible change to a maze, mirroring the editing methods on `Maze`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edit {
+    SetWall {
+        pos: Pos,
+        side: Direction,
+        present: bool,
+    },
+    Carve {
+        pos: Pos,
+        direction: Direction,
+    },
+    SetLabel {
+        pos: Pos,
+        label: String,
+    },
+    InsertRow(usize),
+    InsertCol(usize),
+    RemoveRow(usize),
+    RemoveCol(usize),
+}
+
+impl Edit {
+    /// Makes the change to `maze` through the matching `Maze` method.
+    pub fn apply(&self, maze: &mut Maze) -> Result<(), EditError> {
+        match self {
+            Self::SetWall { pos, side, present } => maze.set_wall(*pos, *side, *present),
+            Self::Carve { pos, direction } => maze.carve(*pos, *direction),
+            Self::SetLabel { pos, label } => maze.set_label(*pos, label),
+            Self::InsertRow(index) => maze.insert_row(*index),
+            Self::InsertCol(index) => maze.insert_col(*index),
+            Self::RemoveRow(index) => maze.remove_row(*index),
+            Self::RemoveCol(index) => maze.remove_col(*index),
+        }
+    }
+
+    /// Returns the rows and columns of the cells the edit can change, before it is made.
+    fn touches(&self, maze: &Maze) -> (RangeInclusive<usize>, RangeInclusive<usize>) {
+        let around = |index: usize, before: usize, after: usize| {
+            index.saturating_sub(before)..=index + after
+        };
+        let all = |len: usize| 0..=len.saturating_sub(1);
+
+        match self {
+            // Adding a wall adds the corners at its ends to every cell around them
+            Self::SetWall { pos, .. } | Self::Carve { pos, .. } => {
+                (around(pos.row, 1, 1), around(pos.col, 1, 1))
+            }
+            Self::SetLabel { pos, .. } => (pos.row..=pos.row, pos.col..=pos.col),
+            Self::InsertRow(index) => (around(*index, 1, 0), all(maze.width())),
+            Self::InsertCol(index) => (all(maze.height()), around(*index, 1, 0)),
+            Self::RemoveRow(index) => (around(*index, 1, 1), all(maze.width())),
+            Self::RemoveCol(index) => (all(maze.height()), around(*index, 1, 1)),
+        }
+    }
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SetWall { pos, side, present } => write!(
+                f,
+                "{} the {} wall of {}",
+                if *present { "add" } else { "remove" },
+                side,
+                pos,
+            ),
+            Self::Carve { pos, direction } => {
+                write!(f, "carve through the {} wall of {}", direction, pos)
+            }
+            Self::SetLabel { pos, label } => write!(f, "label {} {:?}", pos, label),
+            Self::InsertRow(index) => write!(f, "insert row {}", index),
+            Self::InsertCol(index) => write!(f, "insert column {}", index),
+            Self::RemoveRow(index) => write!(f, "remove row {}", index),
+            Self::RemoveCol(index) => write!(f, "remove column {}", index),
+        }
+    }
+}
+
+/// An edit as it was made, with the cells it could change as they were beforehand.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Entry {
+    edit: Edit,
+    before: Vec<(Pos, Cell)>,
+}
+
+impl Entry {
+    /// Makes `edit`, recording what it changes. Nothing is recorded if it fails.
+    fn apply(maze: &mut Maze, edit: Edit) -> Result<Self, EditError> {
+        let (rows, cols) = edit.touches(maze);
+        let before = rows
+            .flat_map(|r| cols.clone().map(move |c| Pos::new(r, c)))
+            .filter_map(|pos| maze.get(pos).map(|cell| (pos, cell.clone())))
+            .collect();
+        edit.apply(maze)?;
+        Ok(Self { edit, before })
+    }
+
+    /// Puts back the cells the edit changed, first undoing any change to the maze's shape.
+    fn undo(&self, maze: &mut Maze) {
+        let removed = |pos: Pos| self.before.iter().any(|(p, _)| *p == pos);
+
+        match self.edit {
+            Edit::InsertRow(index) if index < maze.cells.len() => {
+                maze.cells.remove(index);
+            }
+            Edit::InsertCol(index) => {
+                for row in &mut maze.cells {
+                    if index < row.len() {
+                        row.remove(index);
+                    }
+                }
+            }
+            Edit::RemoveRow(index) if index <= maze.cells.len() => {
+                let len = self.before.iter().filter(|(p, _)| p.row == index).count();
+                maze.cells.insert(index, vec![Cell::default(); len]);
+            }
+            Edit::RemoveCol(index) => {
+                for (r, row) in maze.cells.iter_mut().enumerate() {
+                    if index <= row.len() && removed(Pos::new(r, index)) {
+                        row.insert(index, Cell::default());
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        for (pos, cell) in &self.before {
+            if let Some(slot) = maze.get_mut(*pos) {
+                *slot = cell.clone();
+            }
+        }
+    }
+}
+
+/// A named group of edits that is undone and redone as one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transaction {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+impl Transaction {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the edits in the order they were made.
+    pub fn edits(&self) -> impl Iterator<Item = &Edit> + '_ {
+        self.entries.iter().map(|entry| &entry.edit)
+    }
+}
+
+/// A history of the edits made to one maze, with undo and redo.
+///
+/// Edits made outside a transaction each form their own, named after the edit. Between
+/// `begin` and `commit` they are grouped under the name given to `begin`; nested
+/// transactions join the outermost one.
+///
+/// The log only records changes; the maze is passed to each call and must be the one the
+/// edits were made to. With the `serde` feature the log can be saved, and `replay` makes
+/// its edits again on a fresh copy of the original maze.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EditLog {
+    done: Vec<Transaction>,
+    undone: Vec<Transaction>,
+    open: Option<Transaction>,
+    depth: usize,
+}
+
+impl EditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes `edit` to `maze` and records it, discarding anything that could be redone.
+    /// A refused edit leaves both the maze and the log unchanged.
+    pub fn apply(&mut self, maze: &mut Maze, edit: Edit) -> Result<(), EditError> {
+        let name = edit.to_string();
+        let entry = Entry::apply(maze, edit)?;
+        self.undone.clear();
+
+        match &mut self.open {
+            Some(transaction) => transaction.entries.push(entry),
+            None => {
+                let mut transaction = Transaction::new(name);
+                transaction.entries.push(entry);
+                self.done.push(transaction);
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts grouping edits into a transaction called `name`. If one is already open, the
+    /// edits join it instead and `name` is ignored.
+    pub fn begin(&mut self, name: impl Into<String>) {
+        if self.open.is_none() {
+            self.open = Some(Transaction::new(name));
+        }
+        self.depth += 1;
+    }
+
+    /// Closes the innermost transaction started with `begin`. Closing the outermost records
+    /// it, unless no edits were made. Does nothing if no transaction is open.
+    pub fn commit(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            self.close();
+        }
+    }
+
+    /// Undoes the edits of the open transaction and discards it, however deeply nested.
+    pub fn rollback(&mut self, maze: &mut Maze) {
+        if let Some(transaction) = self.open.take() {
+            for entry in transaction.entries.iter().rev() {
+                entry.undo(maze);
+            }
+        }
+        self.depth = 0;
+    }
+
+    /// Undoes the most recent transaction, committing an open one first. Returns its name,
+    /// or `None` if there is nothing to undo.
+    pub fn undo(&mut self, maze: &mut Maze) -> Option<&str> {
+        self.depth = 0;
+        self.close();
+
+        let transaction = self.done.pop()?;
+        for entry in transaction.entries.iter().rev() {
+            entry.undo(maze);
+        }
+        self.undone.push(transaction);
+        self.undone.last().map(Transaction::name)
+    }
+
+    /// Makes the most recently undone transaction's edits again. Returns its name, or
+    /// `None` if there is nothing to redo.
+    ///
+    /// An edit is only refused if `maze` has been changed outside the log since it was
+    /// undone. The edits before it are then undone again, and the transaction stays
+    /// available to redo.
+    pub fn redo(&mut self, maze: &mut Maze) -> Result<Option<&str>, EditError> {
+        let Some(undone) = self.undone.pop() else {
+            return Ok(None);
+        };
+
+        let mut transaction = Transaction::new(undone.name.clone());
+        let redone = undone.edits().try_for_each(|edit| {
+            transaction.entries.push(Entry::apply(maze, edit.clone())?);
+            Ok(())
+        });
+        if let Err(err) = redone {
+            for entry in transaction.entries.iter().rev() {
+                entry.undo(maze);
+            }
+            self.undone.push(undone);
+            return Err(err);
+        }
+
+        self.done.push(transaction);
+        Ok(self.done.last().map(Transaction::name))
+    }
+
+    /// Returns the name of the transaction `undo` would undo.
+    pub fn undo_name(&self) -> Option<&str> {
+        match &self.open {
+            Some(transaction) if !transaction.entries.is_empty() => Some(transaction.name()),
+            _ => self.done.last().map(Transaction::name),
+        }
+    }
+
+    /// Returns the name of the transaction `redo` would redo.
+    pub fn redo_name(&self) -> Option<&str> {
+        self.undone.last().map(Transaction::name)
+    }
+
+    /// Returns the committed transactions that haven't been undone, oldest first.
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.done
+    }
+
+    /// Makes every committed edit that hasn't been undone again, in order, on `maze`. Given
+    /// the maze the log started from, this rebuilds the edited maze.
+    pub fn replay(&self, maze: &mut Maze) -> Result<(), EditError> {
+        for edit in self.done.iter().flat_map(Transaction::edits) {
+            edit.apply(maze)?;
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        if let Some(transaction) = self.open.take() {
+            if !transaction.entries.is_empty() {
+                self.done.push(transaction);
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Parsing and representation of ASCII mazes.
+//!
+//! The main entry point is [`Maze`], which can be parsed from either the
+//! small or the large ASCII format:
+//!
+//! ```
+//! use maze_parse::prelude::*;
+//!
+//! let maze: Maze = "\
+//! +---+---+
+//! | A | B |
+//! +---+---+"
+//!     .parse()
+//!     .unwrap();
+//!
+//! assert_eq!(maze.cells[0][1].inner_text, " B ");
+//! ```
+
+mod cell;
+mod charset;
+mod connectivity;
+mod convert;
+mod diagnostic;
+mod edges;
+mod edit;
+mod errors;
+mod format;
+mod generate;
+mod grid;
+mod history;
+mod marker;
+mod maze;
+mod packed;
+mod parser;
+mod pos;
+mod render;
+mod solve;
+mod stats;
+mod validate;
+mod walls;
+mod weighted;
+
+pub use crate::{
+    cell::{Cell, CellGeometry, CELL_CHAR_WIDTH, CELL_LINE_HEIGHT, CELL_WALL_PIPES, WALL_DASHES},
+    charset::{Arms, Charset},
+    convert::{ConvertError, Converted, MergePolicy},
+    diagnostic::explain,
+    edges::{EdgeLabel, EdgeList, EdgeListError, EDGE_LIST_VERSION},
+    edit::EditError,
+    errors::{ParseMazeError, Span, SyntaxError},
+    format::{detect_format, Evidence, FormatDetection, MazeFormat},
+    generate::Generator,
+    history::{Edit, EditLog, Transaction},
+    marker::{Marker, MarkerScheme},
+    maze::Maze,
+    packed::{CellFlags, PackedMaze},
+    parser::ContentParser,
+    pos::{Corner, Direction, Pos},
+    solve::Path,
+    stats::MazeStats,
+    validate::{Inconsistency, StrictParseError},
+    walls::WallGrid,
+    weighted::{CostModel, WeightedPath},
+};
+
+/// Commonly used types, for glob importing.
+pub mod prelude {
+    pub use crate::{Cell, Direction, Maze, MazeFormat, ParseMazeError, Pos};
+}
@@ -0,0 +1,138 @@
+use crate::{maze::Maze, pos::Pos};
+
+/// What a cell's inner text marks it as, under some `MarkerScheme`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Marker {
+    /// Where a route through the maze begins.
+    Start,
+    /// Where a route may end. A maze can have several.
+    Goal,
+    /// A numbered point to visit along the way.
+    Waypoint(u32),
+    /// A cell that should not be entered.
+    Blocked,
+    /// Any other text, trimmed of surrounding whitespace.
+    Label(String),
+}
+
+/// Which inner texts mean what. Texts are compared after trimming surrounding whitespace,
+/// so `" S "` and `"S"` are the same marker.
+///
+/// The default reads `S` as the start, `E` and `G` as goals, `#` as blocked and any
+/// number as a waypoint.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarkerScheme {
+    pub start: Vec<String>,
+    pub goals: Vec<String>,
+    pub blocked: Vec<String>,
+    /// Whether texts made only of ASCII digits are waypoints, numbered by their value.
+    pub numbered_waypoints: bool,
+}
+
+impl Default for MarkerScheme {
+    fn default() -> Self {
+        Self {
+            start: vec![String::from("S")],
+            goals: vec![String::from("E"), String::from("G")],
+            blocked: vec![String::from("#")],
+            numbered_waypoints: true,
+        }
+    }
+}
+
+impl MarkerScheme {
+    /// Returns the marker for a cell's inner text, or `None` if the text is blank.
+    pub fn classify(&self, inner_text: &str) -> Option<Marker> {
+        let text = inner_text.trim();
+        let matches = |texts: &[String]| texts.iter().any(|t| t == text);
+
+        if text.is_empty() {
+            None
+        } else if matches(&self.start) {
+            Some(Marker::Start)
+        } else if matches(&self.goals) {
+            Some(Marker::Goal)
+        } else if matches(&self.blocked) {
+            Some(Marker::Blocked)
+        } else if let Some(n) = self
+            .numbered_waypoints
+            .then(|| waypoint_number(text))
+            .flatten()
+        {
+            Some(Marker::Waypoint(n))
+        } else {
+            Some(Marker::Label(String::from(text)))
+        }
+    }
+}
+
+fn waypoint_number(text: &str) -> Option<u32> {
+    if text.bytes().all(|b| b.is_ascii_digit()) {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+impl Maze {
+    /// Iterates over the cells that carry a marker under `scheme`, in row-major order.
+    pub fn markers_with<'a>(
+        &'a self,
+        scheme: &'a MarkerScheme,
+    ) -> impl Iterator<Item = (Pos, Marker)> + 'a {
+        self.iter_row_major()
+            .filter_map(|(pos, cell)| Some((pos, scheme.classify(&cell.inner_text)?)))
+    }
+
+    /// Returns the first start cell under the default `MarkerScheme`.
+    pub fn start(&self) -> Option<Pos> {
+        self.start_with(&MarkerScheme::default())
+    }
+
+    /// Returns the first start cell under `scheme`.
+    pub fn start_with(&self, scheme: &MarkerScheme) -> Option<Pos> {
+        self.markers_with(scheme)
+            .find(|(_, marker)| *marker == Marker::Start)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns every goal cell under the default `MarkerScheme`.
+    pub fn goals(&self) -> Vec<Pos> {
+        self.goals_with(&MarkerScheme::default())
+    }
+
+    /// Returns every goal cell under `scheme`.
+    pub fn goals_with(&self, scheme: &MarkerScheme) -> Vec<Pos> {
+        self.markers_with(scheme)
+            .filter(|(_, marker)| *marker == Marker::Goal)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Returns the waypoints under the default `MarkerScheme`, ordered by number.
+    pub fn waypoints(&self) -> Vec<(u32, Pos)> {
+        self.waypoints_with(&MarkerScheme::default())
+    }
+
+    /// Returns the waypoints under `scheme`, ordered by number.
+    pub fn waypoints_with(&self, scheme: &MarkerScheme) -> Vec<(u32, Pos)> {
+        let mut waypoints = self
+            .markers_with(scheme)
+            .filter_map(|(pos, marker)| match marker {
+                Marker::Waypoint(n) => Some((n, pos)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        waypoints.sort();
+        waypoints
+    }
+
+    /// Returns the first cell, in row-major order, whose inner text is `label` once both are
+    /// trimmed of surrounding whitespace.
+    pub fn find_label(&self, label: &str) -> Option<Pos> {
+        let label = label.trim();
+        self.iter_row_major()
+            .find(|(_, cell)| cell.inner_text.trim() == label)
+            .map(|(pos, _)| pos)
+    }
+}
@@ -1,56 +1,156 @@
-use std::{fs, path::Path, str::FromStr};
+use std::{fmt, fs, path::Path, str::FromStr};
 
 use anyhow::{Error, Result};
 
 use crate::{
-    cell::{Cell, CELL_CHAR_WIDTH, CELL_LINE_HEIGHT},
-    errors::ParseMazeError,
+    cell::{Cell, CellGeometry},
+    charset::Charset,
+    errors::{ParseMazeError, Span, SyntaxError},
+    format::{detect_format, MazeFormat},
     parser::ContentParser,
+    pos::Pos,
+    render,
 };
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Maze {
     pub cells: Vec<Vec<Cell>>,
 }
 
 impl Maze {
     pub fn new_from_file(path: impl AsRef<Path>) -> Result<Self> {
-        Self::from_str(&fs::read_to_string(path)?).map_err(|err| Error::new(err))
+        Self::from_str(&fs::read_to_string(path)?).map_err(Error::new)
     }
 
-    pub fn parse_sm(s: impl Into<String>) -> Result<Self> {
-        Self::do_parse(s, 0)
+    pub fn parse_sm(s: impl Into<String>) -> Result<Self, SyntaxError> {
+        Self::parse_sm_with(s, CellGeometry::default())
     }
 
-    pub fn parse_lg(s: impl Into<String>) -> Result<Self> {
-        Self::do_parse(s, 1)
+    pub fn parse_lg(s: impl Into<String>) -> Result<Self, SyntaxError> {
+        Self::parse_lg_with(s, CellGeometry::default())
     }
 
-    fn do_parse(s: impl Into<String>, offset: isize) -> Result<Self> {
-        let mut parser = ContentParser::new(s.into());
+    /// Parses a maze drawn with box-drawing characters, laid out like the small format.
+    pub fn parse_box(s: impl Into<String>) -> Result<Self, SyntaxError> {
+        Self::parse_box_with(s, CellGeometry::default())
+    }
+
+    /// Parses a small format maze whose cells have the given geometry.
+    pub fn parse_sm_with(
+        s: impl Into<String>,
+        geometry: CellGeometry,
+    ) -> Result<Self, SyntaxError> {
+        Self::parse_as(s, MazeFormat::Small, geometry)
+    }
+
+    /// Parses a large format maze whose cells have the given geometry.
+    pub fn parse_lg_with(
+        s: impl Into<String>,
+        geometry: CellGeometry,
+    ) -> Result<Self, SyntaxError> {
+        Self::parse_as(s, MazeFormat::Large, geometry)
+    }
+
+    /// Parses a box-drawing maze whose cells have the given geometry.
+    pub fn parse_box_with(
+        s: impl Into<String>,
+        geometry: CellGeometry,
+    ) -> Result<Self, SyntaxError> {
+        Self::parse_as(s, MazeFormat::BoxDrawing, geometry)
+    }
+
+    /// Parses a maze in the given format whose cells have the given geometry.
+    pub fn parse_as(
+        s: impl Into<String>,
+        format: MazeFormat,
+        geometry: CellGeometry,
+    ) -> Result<Self, SyntaxError> {
         let mut cells = Vec::new();
+        Self::parse_rows(s, format, geometry, |row| cells.push(row))?;
+        Ok(Maze { cells })
+    }
+
+    /// Returns the geometry of the maze's cells, taken from the inner text of the first cell.
+    pub fn geometry(&self) -> CellGeometry {
+        match self.cells.first().and_then(|row| row.first()) {
+            Some(cell) => cell.geometry(),
+            None => CellGeometry::default(),
+        }
+    }
+
+    /// Renders the maze in the small format read by `parse_sm`. This is also what the
+    /// `Display` impl writes.
+    ///
+    /// Walls and corners shared by neighbouring cells are merged, so canonical small-format
+    /// input renders back to exactly the text it was parsed from.
+    pub fn render_sm(&self) -> String {
+        render::render_shared(self, Charset::Ascii)
+    }
+
+    /// Renders the maze in the large format read by `parse_lg`, with doubled `++` corners
+    /// and `||` walls between neighbouring cells.
+    ///
+    /// Each cell's own walls and corners are written exactly, so large-format input,
+    /// one-sided walls included, renders back to exactly the text it was parsed from.
+    pub fn render_lg(&self) -> String {
+        render::render_doubled(self)
+    }
+
+    /// Renders the maze with box-drawing characters, in the layout read by `parse_box`.
+    pub fn render_box(&self) -> String {
+        render::render_shared(self, Charset::BoxDrawing)
+    }
+
+    /// Parses a maze in the given format a row of cells at a time, handing each row to
+    /// `emit_row` as soon as it is read, so callers can store it however they like.
+    pub(crate) fn parse_rows(
+        s: impl Into<String>,
+        format: MazeFormat,
+        geometry: CellGeometry,
+        mut emit_row: impl FnMut(Vec<Cell>),
+    ) -> Result<(), SyntaxError> {
+        let offset = format.offset();
+        let charset = format.charset();
+        let cell_char_width = geometry.char_width();
+        let cell_line_height = geometry.line_height();
+
+        let mut parser = ContentParser::new(s.into());
+        let layout = Layout::new(&parser, offset as usize, geometry);
+        layout.check_ragged(&mut parser)?;
+
+        let mut rows = 0;
 
         while parser.curr_char().is_ok() {
             let row_start = parser.curr_pos();
             let mut row = Vec::new();
 
             loop {
-                let cell_lines = (0..CELL_LINE_HEIGHT)
+                let cell_start = parser.curr_pos();
+                let pos = Pos::new(rows, row.len());
+
+                let cell_lines = (0..cell_line_height)
                     .map(|i| {
-                        if i > 0 {
-                            parser.next_line()?;
+                        if i > 0 && parser.next_line().is_err() {
+                            return Err(SyntaxError::TruncatedCell {
+                                span: layout.cell_span(parser.curr_pos()),
+                                cell: pos,
+                            });
                         }
-                        parser.slice(CELL_CHAR_WIDTH)
+                        layout.slice_cell_line(&parser, pos)
                     })
-                    .collect::<Result<Vec<String>>>()?;
+                    .collect::<Result<Vec<String>, SyntaxError>>()?;
 
-                row.push(cell_lines.join("\n").parse::<Cell>()?);
+                row.push(
+                    Cell::parse_with(&cell_lines.join("\n"), charset)
+                        .map_err(|err| err.in_maze(cell_start, pos))?,
+                );
 
                 parser
-                    .move_lines(-(CELL_LINE_HEIGHT as isize) + 1)
+                    .move_lines(-(cell_line_height as isize) + 1)
                     .expect("should be at the start of cell row");
 
-                if parser.move_cols(CELL_CHAR_WIDTH as isize + offset).is_err() {
+                if parser.move_cols(cell_char_width as isize + offset).is_err() {
                     break;
                 }
 
@@ -59,14 +159,15 @@ impl Maze {
                     .expect("should be on the right edge of previous cell");
             }
 
-            cells.push(row);
+            emit_row(row);
+            rows += 1;
 
             parser
                 .go_to_pos(row_start)
                 .expect("should return to start of current row");
 
             if parser
-                .move_lines(CELL_LINE_HEIGHT as isize + offset)
+                .move_lines(cell_line_height as isize + offset)
                 .is_err()
             {
                 break;
@@ -77,24 +178,124 @@ impl Maze {
                 .expect("should be at the bottom edge of the row");
         }
 
-        Ok(Maze { cells })
+        Ok(())
+    }
+}
+
+/// Where cells fall in the source of a maze, used to place errors.
+struct Layout {
+    geometry: CellGeometry,
+    /// 1 for the large format, where neighbouring cells do not share walls.
+    offset: usize,
+    /// Columns and lines between the starts of neighbouring cells.
+    col_stride: usize,
+    line_stride: usize,
+    /// The number of rows and columns of cells the content reaches into.
+    rows: usize,
+    cols: usize,
+}
+
+impl Layout {
+    fn new(parser: &ContentParser, offset: usize, geometry: CellGeometry) -> Self {
+        let col_stride = geometry.width + 1 + offset;
+        let line_stride = geometry.height + 1 + offset;
+        let width = parser.line_len(1).unwrap_or(0);
+        let lines = parser.line_count();
+
+        Self {
+            geometry,
+            offset,
+            col_stride,
+            line_stride,
+            rows: (lines.saturating_sub(1) + offset).div_ceil(line_stride),
+            cols: (width.saturating_sub(1) + offset).div_ceil(col_stride),
+        }
+    }
+
+    /// Returns the cell that the 1-based position falls in. A wall shared by two
+    /// cells belongs to the cell above or to the left of it.
+    fn cell_at(&self, line: usize, col: usize) -> Pos {
+        let index = |n: usize, stride| n.saturating_sub(2 - self.offset) / stride;
+        Pos::new(
+            index(line, self.line_stride).min(self.rows.saturating_sub(1)),
+            index(col, self.col_stride).min(self.cols.saturating_sub(1)),
+        )
+    }
+
+    /// Returns the span of a cell's line starting at `(line, col)`.
+    fn cell_span(&self, (line, col): (usize, usize)) -> Span {
+        Span::new(line, col, col + self.geometry.char_width() - 1)
+    }
+
+    /// Checks that every line is as wide as the first, ignoring trailing whitespace.
+    fn check_ragged(&self, parser: &mut ContentParser) -> Result<(), SyntaxError> {
+        let expected = parser.line_len(1).unwrap_or(0);
+
+        for line in 2..=parser.line_count() {
+            let found = parser.line_len(line).unwrap_or(0);
+            let overhang_is_blank = found <= expected
+                || (parser.go_to(line, expected + 1).is_ok()
+                    && parser
+                        .slice(found - expected)
+                        .is_ok_and(|s| s.trim().is_empty()));
+
+            if found < expected || !overhang_is_blank {
+                let start_col = found.min(expected) + 1;
+                return Err(SyntaxError::RaggedRow {
+                    span: Span::new(line, start_col, found.max(expected)),
+                    cell: self.cell_at(line, start_col),
+                    expected,
+                    found,
+                });
+            }
+        }
+
+        let _ = parser.go_to(1, 1);
+        Ok(())
+    }
+
+    /// Slices the line of the cell at the parser's position, reporting a cell that
+    /// runs off the line or splits a wide character.
+    fn slice_cell_line(&self, parser: &ContentParser, cell: Pos) -> Result<String, SyntaxError> {
+        let (line, col) = parser.curr_pos();
+        let span = self.cell_span((line, col));
+
+        if parser.line_len(line).unwrap_or(0) < span.end_col {
+            return Err(SyntaxError::TruncatedCell { span, cell });
+        }
+
+        parser.slice(self.geometry.char_width()).map_err(|_| {
+            let split_col = if parser.is_char_start(line, col) {
+                span.end_col
+            } else {
+                col
+            };
+            SyntaxError::UnexpectedChar {
+                span: Span::at(line, split_col),
+                cell,
+                found: parser
+                    .char_at(line, split_col)
+                    .expect("split column should be inside the line"),
+                expected: "a character that fits inside the cell",
+            }
+        })
+    }
+}
+
+impl fmt::Display for Maze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render_sm())
     }
 }
 
 impl FromStr for Maze {
     type Err = ParseMazeError;
 
+    /// Parses a maze in whichever format `detect_format` finds, inferring its geometry.
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let mut sm_err: Option<Error> = None;
-
-        Self::parse_sm(s)
-            .or_else(|err| {
-                sm_err = Some(err);
-                Self::parse_lg(s)
-            })
-            .map_err(|err| ParseMazeError {
-                sm_err: sm_err,
-                lg_err: Some(err),
-            })
+        let format = detect_format(s).format;
+        let geometry = CellGeometry::infer(s, format.charset());
+
+        Self::parse_as(s, format, geometry).map_err(|err| ParseMazeError::new(format, err))
     }
 }
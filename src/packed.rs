@@ -0,0 +1,343 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::{
+    cell::{Cell, CellGeometry},
+    errors::{ParseMazeError, SyntaxError},
+    format::{detect_format, MazeFormat},
+    maze::Maze,
+    pos::{Corner, Direction, Pos},
+};
+
+/// A cell's walls and corners packed into one byte.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct CellFlags(u8);
+
+impl CellFlags {
+    pub const WALL_TOP: Self = Self(1 << 0);
+    pub const WALL_RIGHT: Self = Self(1 << 1);
+    pub const WALL_BOTTOM: Self = Self(1 << 2);
+    pub const WALL_LEFT: Self = Self(1 << 3);
+    pub const CORNER_TOP_LEFT: Self = Self(1 << 4);
+    pub const CORNER_TOP_RIGHT: Self = Self(1 << 5);
+    pub const CORNER_BOTTOM_RIGHT: Self = Self(1 << 6);
+    pub const CORNER_BOTTOM_LEFT: Self = Self(1 << 7);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn all() -> Self {
+        Self(u8::MAX)
+    }
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether every flag set in `other` is also set in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets or clears the flags in `other`.
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+
+    /// Returns the flag for the wall on `side`.
+    pub const fn wall(side: Direction) -> Self {
+        match side {
+            Direction::Up => Self::WALL_TOP,
+            Direction::Right => Self::WALL_RIGHT,
+            Direction::Down => Self::WALL_BOTTOM,
+            Direction::Left => Self::WALL_LEFT,
+        }
+    }
+
+    /// Returns the flag for `corner`.
+    pub const fn corner(corner: Corner) -> Self {
+        match corner {
+            Corner::TopLeft => Self::CORNER_TOP_LEFT,
+            Corner::TopRight => Self::CORNER_TOP_RIGHT,
+            Corner::BottomRight => Self::CORNER_BOTTOM_RIGHT,
+            Corner::BottomLeft => Self::CORNER_BOTTOM_LEFT,
+        }
+    }
+
+    pub const fn has_wall(self, side: Direction) -> bool {
+        self.contains(Self::wall(side))
+    }
+
+    pub const fn has_corner(self, corner: Corner) -> bool {
+        self.contains(Self::corner(corner))
+    }
+
+    /// Returns a `Cell` with these walls and corners and the given inner text.
+    pub fn to_cell(self, inner_text: impl Into<String>) -> Cell {
+        Cell {
+            wall_top: self.contains(Self::WALL_TOP),
+            wall_bottom: self.contains(Self::WALL_BOTTOM),
+            wall_left: self.contains(Self::WALL_LEFT),
+            wall_right: self.contains(Self::WALL_RIGHT),
+            corner_top_left: self.contains(Self::CORNER_TOP_LEFT),
+            corner_top_right: self.contains(Self::CORNER_TOP_RIGHT),
+            corner_bottom_left: self.contains(Self::CORNER_BOTTOM_LEFT),
+            corner_bottom_right: self.contains(Self::CORNER_BOTTOM_RIGHT),
+            inner_text: inner_text.into(),
+        }
+    }
+}
+
+impl From<&Cell> for CellFlags {
+    fn from(cell: &Cell) -> Self {
+        let mut flags = Self::empty();
+        flags.set(Self::WALL_TOP, cell.wall_top);
+        flags.set(Self::WALL_RIGHT, cell.wall_right);
+        flags.set(Self::WALL_BOTTOM, cell.wall_bottom);
+        flags.set(Self::WALL_LEFT, cell.wall_left);
+        flags.set(Self::CORNER_TOP_LEFT, cell.corner_top_left);
+        flags.set(Self::CORNER_TOP_RIGHT, cell.corner_top_right);
+        flags.set(Self::CORNER_BOTTOM_RIGHT, cell.corner_bottom_right);
+        flags.set(Self::CORNER_BOTTOM_LEFT, cell.corner_bottom_left);
+        flags
+    }
+}
+
+impl fmt::Debug for CellFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CellFlags({:#010b})", self.0)
+    }
+}
+
+/// A maze stored compactly: one byte of `CellFlags` per cell in a single row-major `Vec`,
+/// with inner text kept only for cells that aren't blank and shared between cells with the
+/// same text.
+///
+/// A 2000x2000 `Maze` takes hundreds of megabytes, mostly in per-cell strings; packed, its
+/// walls and corners take 4 MB. Cells are read back as `Cell` values, so code written
+/// against `Maze` can be pointed at one with little change.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackedMaze {
+    width: usize,
+    height: usize,
+    geometry: CellGeometry,
+    flags: Vec<CellFlags>,
+    /// The inner text of a blank cell, `geometry.width` spaces on each of its lines.
+    blank: String,
+    /// Every distinct non-blank inner text.
+    texts: Vec<String>,
+    /// The cell index and index into `texts` of each cell with non-blank inner text,
+    /// sorted by cell index.
+    labels: Vec<(usize, u32)>,
+}
+
+impl PackedMaze {
+    /// Packs `maze`. Rows shorter than the longest are padded with cells that have no
+    /// walls or corners, so the packed maze is always rectangular.
+    pub fn pack(maze: &Maze) -> Self {
+        let mut packer = Packer::new(maze.width(), maze.geometry());
+        for row in &maze.cells {
+            packer.push_row(row);
+        }
+        packer.packed
+    }
+
+    /// Parses a maze in the given format whose cells have the given geometry, as
+    /// `Maze::parse_as` does, packing each row as it is read rather than building the
+    /// whole `Maze` first.
+    pub fn parse_as(
+        s: impl Into<String>,
+        format: MazeFormat,
+        geometry: CellGeometry,
+    ) -> Result<Self, SyntaxError> {
+        let mut packer = None;
+        Maze::parse_rows(s, format, geometry, |row| {
+            // Every line is as wide as the first, so every row has as many cells
+            packer
+                .get_or_insert_with(|| Packer::new(row.len(), row[0].geometry()))
+                .push_row(&row);
+        })?;
+
+        Ok(packer
+            .unwrap_or_else(|| Packer::new(0, CellGeometry::default()))
+            .packed)
+    }
+
+    /// Expands the packed maze back into a `Maze`.
+    pub fn unpack(&self) -> Maze {
+        let cells = (0..self.height)
+            .map(|row| {
+                (0..self.width)
+                    .map(|col| self.cell_at(row * self.width + col))
+                    .collect()
+            })
+            .collect();
+        Maze { cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn geometry(&self) -> CellGeometry {
+        self.geometry
+    }
+
+    /// Returns the walls and corners of the cell at `pos`, or `None` if it is outside the maze.
+    pub fn flags(&self, pos: Pos) -> Option<CellFlags> {
+        self.index(pos).map(|index| self.flags[index])
+    }
+
+    /// Returns the inner text of the cell at `pos`, or `None` if it is outside the maze.
+    pub fn inner_text(&self, pos: Pos) -> Option<&str> {
+        self.index(pos).map(|index| self.text_at(index))
+    }
+
+    /// Returns a copy of the cell at `pos`, or `None` if it is outside the maze.
+    pub fn get(&self, pos: Pos) -> Option<Cell> {
+        self.index(pos).map(|index| self.cell_at(index))
+    }
+
+    /// Iterates over copies of the cells a row at a time, from the top left.
+    pub fn iter_row_major(&self) -> impl Iterator<Item = (Pos, Cell)> + '_ {
+        (0..self.flags.len()).map(|index| {
+            (
+                Pos::new(index / self.width, index % self.width),
+                self.cell_at(index),
+            )
+        })
+    }
+
+    /// Returns whether there is an open passage from `pos` to its neighbour in `direction`,
+    /// as `Maze::can_move` does.
+    pub fn can_move(&self, pos: Pos, direction: Direction) -> bool {
+        let Some(next) = pos.step(direction) else {
+            return false;
+        };
+        match (self.flags(pos), self.flags(next)) {
+            (Some(flags), Some(neighbour)) => {
+                !flags.has_wall(direction) && !neighbour.has_wall(direction.opposite())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the neighbours of `pos` that can be moved to, as `Maze::open_neighbors` does.
+    pub fn open_neighbors(&self, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+        Direction::ALL.into_iter().filter_map(move |direction| {
+            self.can_move(pos, direction)
+                .then(|| pos.step(direction))
+                .flatten()
+                .map(|next| (direction, next))
+        })
+    }
+
+    fn index(&self, pos: Pos) -> Option<usize> {
+        (pos.row < self.height && pos.col < self.width).then(|| pos.row * self.width + pos.col)
+    }
+
+    fn text_at(&self, index: usize) -> &str {
+        match self.labels.binary_search_by_key(&index, |&(cell, _)| cell) {
+            Ok(label) => &self.texts[self.labels[label].1 as usize],
+            Err(_) => &self.blank,
+        }
+    }
+
+    fn cell_at(&self, index: usize) -> Cell {
+        self.flags[index].to_cell(self.text_at(index))
+    }
+}
+
+/// Builds a `PackedMaze` a row at a time, sharing the text of cells with the same label.
+struct Packer {
+    packed: PackedMaze,
+    text_ids: HashMap<String, u32>,
+}
+
+impl Packer {
+    fn new(width: usize, geometry: CellGeometry) -> Self {
+        Self {
+            packed: PackedMaze {
+                width,
+                height: 0,
+                geometry,
+                flags: Vec::new(),
+                blank: vec![" ".repeat(geometry.width); geometry.height].join("\n"),
+                texts: Vec::new(),
+                labels: Vec::new(),
+            },
+            text_ids: HashMap::new(),
+        }
+    }
+
+    /// Appends a row of at most `width` cells, padding it out with empty cells.
+    fn push_row(&mut self, row: &[Cell]) {
+        let packed = &mut self.packed;
+        let row_start = packed.flags.len();
+
+        for cell in row {
+            if cell.inner_text != packed.blank {
+                let id = match self.text_ids.get(&cell.inner_text) {
+                    Some(&id) => id,
+                    None => {
+                        let id = packed.texts.len() as u32;
+                        packed.texts.push(cell.inner_text.clone());
+                        self.text_ids.insert(cell.inner_text.clone(), id);
+                        id
+                    }
+                };
+                packed.labels.push((packed.flags.len(), id));
+            }
+            packed.flags.push(CellFlags::from(cell));
+        }
+
+        packed
+            .flags
+            .resize(row_start + packed.width, CellFlags::empty());
+        packed.height += 1;
+    }
+}
+
+impl Maze {
+    /// Packs the maze into a `PackedMaze`.
+    pub fn pack(&self) -> PackedMaze {
+        PackedMaze::pack(self)
+    }
+}
+
+impl From<&Maze> for PackedMaze {
+    fn from(maze: &Maze) -> Self {
+        Self::pack(maze)
+    }
+}
+
+impl From<&PackedMaze> for Maze {
+    fn from(packed: &PackedMaze) -> Self {
+        packed.unpack()
+    }
+}
+
+impl FromStr for PackedMaze {
+    type Err = ParseMazeError;
+
+    /// Parses a maze in whichever format `detect_format` finds, inferring its geometry,
+    /// as `Maze::from_str` does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = detect_format(s).format;
+        let geometry = CellGeometry::infer(s, format.charset());
+
+        Self::parse_as(s, format, geometry).map_err(|err| ParseMazeError::new(format, err))
+    }
+}
@@ -1,39 +1,141 @@
-use std::usize;
+use std::{iter, ops::Range};
 
 use anyhow::{anyhow, Result};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// A single line of content, indexed by display column.
+///
+/// Columns are measured in terminal display width, so a wide character such as
+/// an emoji occupies two columns and a combining sequence such as `e\u{301}`
+/// occupies one.
+struct Line {
+    /// Byte range of the line in the content, excluding the line terminator.
+    range: Range<usize>,
+    /// Byte offset (relative to the line) of the grapheme covering each column.
+    /// `None` for ASCII lines, where columns and bytes coincide.
+    col_offsets: Option<Vec<usize>>,
+}
+
+impl Line {
+    fn new(content: &str, range: Range<usize>) -> Self {
+        let text = &content[range.clone()];
+        let col_offsets = (!text.is_ascii()).then(|| {
+            text.grapheme_indices(true)
+                .flat_map(|(offset, grapheme)| iter::repeat_n(offset, grapheme.width().max(1)))
+                .collect()
+        });
+
+        Self { range, col_offsets }
+    }
+
+    /// Returns the width of the line in columns.
+    fn width(&self) -> usize {
+        match &self.col_offsets {
+            Some(offsets) => offsets.len(),
+            None => self.range.len(),
+        }
+    }
+
+    /// Returns the byte offset of the grapheme covering the given 1-based column.
+    fn offset(&self, col: usize) -> Option<usize> {
+        let i = col.checked_sub(1)?;
+        match &self.col_offsets {
+            Some(offsets) => offsets.get(i).copied(),
+            None => (i < self.range.len()).then_some(i),
+        }
+    }
+
+    /// Returns whether a grapheme boundary falls right before the given 1-based column.
+    /// The column one past the end of the line is a boundary.
+    fn is_boundary(&self, col: usize) -> bool {
+        match &self.col_offsets {
+            Some(offsets) => {
+                col == 1
+                    || col == offsets.len() + 1
+                    || (col > 1 && col <= offsets.len() && offsets[col - 1] != offsets[col - 2])
+            }
+            None => col >= 1 && col <= self.range.len() + 1,
+        }
+    }
+
+    /// Returns the byte offset at which the given 1-based column boundary starts.
+    fn boundary_offset(&self, col: usize) -> usize {
+        self.offset(col).unwrap_or(self.range.len())
+    }
+}
 
 pub struct ContentParser {
     content: String,
+    lines: Vec<Line>,
     line: usize,
     col: usize,
 }
 
 impl ContentParser {
     pub fn new(content: impl Into<String>) -> Self {
-        return Self {
-            content: content.into(),
+        let content = content.into();
+        let lines = Self::index_lines(&content);
+
+        Self {
+            content,
+            lines,
             line: 1,
             col: 1,
-        };
+        }
+    }
+
+    /// Splits `content` into lines once, matching the lines yielded by `str::lines`.
+    fn index_lines(content: &str) -> Vec<Line> {
+        let base = content.as_ptr() as usize;
+        content
+            .lines()
+            .map(|line| {
+                let start = line.as_ptr() as usize - base;
+                Line::new(content, start..start + line.len())
+            })
+            .collect()
+    }
+
+    /// Returns the given 1-based line, if it exists.
+    fn get_line(&self, line: usize) -> Option<&Line> {
+        self.lines.get(line.checked_sub(1)?)
+    }
+
+    /// Returns the width in columns of the given 1-based line, if it exists.
+    pub fn line_len(&self, line: usize) -> Option<usize> {
+        self.get_line(line).map(Line::width)
+    }
+
+    /// Returns whether a character starts at the given 1-based position, rather than
+    /// the position being covered by the trailing half of a wide character.
+    /// The column one past the end of a line counts as a character start.
+    pub fn is_char_start(&self, line: usize, col: usize) -> bool {
+        self.get_line(line).is_some_and(|l| l.is_boundary(col))
+    }
+
+    /// Returns the number of lines in the content.
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
     }
 
     /// Returns the current character at the parser's position.
+    /// For a wide character, every column it covers returns that character.
     pub fn curr_char(&self) -> Result<char> {
-        let lines: Vec<&str> = self.content.lines().collect();
-
-        if self.line == 0
-            || self.col == 0
-            || self.line > lines.len()
-            || self.col > lines[self.line - 1].len()
-        {
-            return Err(anyhow!(
+        self.char_at(self.line, self.col).ok_or_else(|| {
+            anyhow!(
                 "Invalid position: Out of bounds (line {}, col {}).",
                 self.line,
                 self.col,
-            ));
-        }
+            )
+        })
+    }
 
-        Ok(lines[self.line - 1].chars().nth(self.col - 1).unwrap())
+    /// Returns the character at the given 1-based position, if it exists.
+    pub fn char_at(&self, line: usize, col: usize) -> Option<char> {
+        let line = self.get_line(line)?;
+        let offset = line.offset(col)?;
+        self.content[line.range.start + offset..].chars().next()
     }
 
     /// Returns the parser's current position (line, col).
@@ -43,26 +145,24 @@ impl ContentParser {
 
     /// Moves the parser to the specified line and column, returning the character at the new position.
     pub fn go_to(&mut self, line: usize, col: usize) -> Result<char> {
-        let lines: Vec<&str> = self.content.lines().collect();
-
         if line == 0 || col == 0 {
             return Err(anyhow!(
                 "Invalid position: line and column must be greater than 0.",
             ));
         }
 
-        if line > lines.len() || col > lines[line - 1].len() {
-            return Err(anyhow!(
+        let c = self.char_at(line, col).ok_or_else(|| {
+            anyhow!(
                 "Invalid position: Out of bounds (line {}, col {})",
                 line,
                 col,
-            ));
-        }
+            )
+        })?;
 
         self.line = line;
         self.col = col;
 
-        Ok(lines[line - 1].chars().nth(col - 1).unwrap())
+        Ok(c)
     }
 
     /// Moves the parser to the specified position, returning the character at the new position.
@@ -103,23 +203,19 @@ impl ContentParser {
 
     /// Move to the end of the current line
     pub fn end_of_line(&mut self) -> Result<char> {
-        let lines: Vec<&str> = self.content.lines().collect();
-        let line_length = lines[self.line - 1].len();
+        let line_length = self
+            .line_len(self.line)
+            .ok_or_else(|| anyhow!("Invalid position: Line {} is out of bounds.", self.line))?;
         self.go_to(self.line, line_length)
     }
 
     /// Moves the parser `n` columns forward or backward.
     /// Positive `n` moves forward, negative `n` moves backward.
     pub fn move_cols(&mut self, n: isize) -> Result<()> {
-        let lines: Vec<&str> = self.content.lines().collect();
-        if self.line == 0 || self.line > lines.len() {
-            return Err(anyhow!(
-                "Invalid move: Current line {} is out of bounds.",
-                self.line,
-            ));
-        }
+        let line_length = self.line_len(self.line).ok_or_else(|| {
+            anyhow!("Invalid move: Current line {} is out of bounds.", self.line,)
+        })?;
 
-        let line_length = lines[self.line - 1].len();
         let new_col = (self.col as isize + n) as usize;
         if new_col == 0 || new_col > line_length {
             return Err(anyhow!(
@@ -135,7 +231,7 @@ impl ContentParser {
     /// Positive `n` moves forward, negative `n` moves backward.
     pub fn move_lines(&mut self, n: isize) -> Result<()> {
         let new_line = (self.line as isize + n) as usize;
-        if new_line == 0 || new_line > self.content.lines().count() {
+        if new_line == 0 || new_line > self.line_count() {
             return Err(anyhow!("Invalid move: Line {} is out of bounds.", new_line));
         }
         self.line = new_line;
@@ -148,13 +244,12 @@ impl ContentParser {
     pub fn move_pos(&mut self, n_lines: isize, n_cols: isize) -> Result<()> {
         // Move lines first
         let new_line = (self.line as isize + n_lines) as usize;
-        if new_line == 0 || new_line > self.content.lines().count() {
+        if new_line == 0 || new_line > self.line_count() {
             return Err(anyhow!("Invalid move: Line {} is out of bounds.", new_line));
         }
 
         // Move columns
-        let lines: Vec<&str> = self.content.lines().collect();
-        let line_length = lines[new_line - 1].len();
+        let line_length = self.lines[new_line - 1].width();
         let new_col = (self.col as isize + n_cols) as usize;
         if new_col == 0 || new_col > line_length {
             return Err(anyhow!(
@@ -172,18 +267,12 @@ impl ContentParser {
     /// Returns a slice of the content starting from the current position
     /// with the specified width (number of columns).
     pub fn slice(&self, width: usize) -> Result<String> {
-        let lines: Vec<&str> = self.content.lines().collect();
-
         // Ensure the current position is valid
-        if self.line == 0 || self.line > lines.len() {
-            return Err(anyhow!(
-                "Invalid position: Line {} is out of bounds.",
-                self.line,
-            ));
-        }
+        let line = self
+            .get_line(self.line)
+            .ok_or_else(|| anyhow!("Invalid position: Line {} is out of bounds.", self.line))?;
 
-        let line = lines[self.line - 1];
-        if self.col == 0 || self.col > line.len() {
+        if self.col == 0 || self.col > line.width() {
             return Err(anyhow!(
                 "Invalid position: Column {} is out of bounds.",
                 self.col,
@@ -192,15 +281,28 @@ impl ContentParser {
 
         // Ensure the slice does not exceed the line's length
         let end_col = self.col + width - 1;
-        if end_col > line.len() {
+        if end_col > line.width() {
             return Err(anyhow!(
                 "Invalid slice: End column {} exceeds line length {}.",
                 end_col,
-                line.len(),
+                line.width(),
             ));
         }
 
+        // Ensure the slice does not cut through a wide character
+        for col in [self.col, end_col + 1] {
+            if !line.is_boundary(col) {
+                return Err(anyhow!(
+                    "Invalid slice: Column {} splits a wide character on line {}.",
+                    col,
+                    self.line,
+                ));
+            }
+        }
+
         // Extract and return the slice
-        Ok(line[self.col - 1..end_col].to_owned())
+        let start = line.range.start + line.boundary_offset(self.col);
+        let end = line.range.start + line.boundary_offset(end_col + 1);
+        Ok(self.content[start..end].to_owned())
     }
 }
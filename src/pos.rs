@@ -0,0 +1,94 @@
+use std::fmt;
+
+/// The coordinate of a cell in a maze, as a zero-based row and column.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Returns the position one step in `direction`, or `None` if it would leave the first
+    /// row or column.
+    pub fn step(self, direction: Direction) -> Option<Pos> {
+        match direction {
+            Direction::Up => Some(Pos::new(self.row.checked_sub(1)?, self.col)),
+            Direction::Right => Some(Pos::new(self.row, self.col + 1)),
+            Direction::Down => Some(Pos::new(self.row + 1, self.col)),
+            Direction::Left => Some(Pos::new(self.row, self.col.checked_sub(1)?)),
+        }
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self::new(row, col)
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// One of the four sides of a cell, or the direction of a move towards that side.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All four directions, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Returns the direction pointing the other way.
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Up => "top",
+            Self::Right => "right",
+            Self::Down => "bottom",
+            Self::Left => "left",
+        })
+    }
+}
+
+/// One of the four corners of a cell.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl fmt::Display for Corner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::TopLeft => "top left",
+            Self::TopRight => "top right",
+            Self::BottomLeft => "bottom left",
+            Self::BottomRight => "bottom right",
+        })
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    cell::Cell,
+    charset::{Arms, Charset},
+    maze::Maze,
+};
+
+/// Renders `maze` in the shared-wall layout of the small format, drawn with `charset`.
+///
+/// Walls and corners shared by neighbouring cells are merged, so a wall is drawn
+/// if either cell on its side has it.
+pub(crate) fn render_shared(maze: &Maze, charset: Charset) -> String {
+    let geometry = maze.geometry();
+    let rows = maze.cells.len();
+    if rows == 0 {
+        // Without rows there is no boundary to draw, so empty input round-trips
+        return String::new();
+    }
+    let mut lines = Vec::with_capacity(rows * (geometry.height + 1) + 1);
+
+    for r in 0..=rows {
+        let cols = row_len(maze, r);
+
+        // Boundary line above row `r` (or below the last row)
+        let mut line = String::new();
+        for c in 0..=cols {
+            line.push(corner(maze, charset, r, c));
+            if c < cols {
+                line.push_str(&wall_segment(
+                    charset,
+                    horizontal_wall(maze, r, c),
+                    geometry.width,
+                ));
+            }
+        }
+        lines.push(line);
+
+        if r == rows {
+            break;
+        }
+
+        // Interior lines of row `r`
+        let mut inner_lines = maze.cells[r]
+            .iter()
+            .map(|cell| cell.inner_text.split('\n'))
+            .collect::<Vec<_>>();
+        for _ in 0..geometry.height {
+            let mut line = String::new();
+            for (c, inner) in inner_lines.iter_mut().enumerate() {
+                line.push(vertical_char(charset, vertical_wall(maze, r, c)));
+                line.push_str(inner.next().unwrap_or_default());
+            }
+            line.push(vertical_char(charset, vertical_wall(maze, r, cols)));
+            lines.push(line);
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Renders `maze` in the doubled-boundary layout of the large format, where every cell
+/// draws its own walls and corners.
+///
+/// Nothing is merged, so one-sided walls are written exactly as the cell records them.
+pub(crate) fn render_doubled(maze: &Maze) -> String {
+    let charset = Charset::Ascii;
+    let geometry = maze.geometry();
+    let mut lines = Vec::with_capacity(maze.cells.len() * (geometry.height + 2));
+
+    for row in &maze.cells {
+        let mut top = String::new();
+        let mut bottom = String::new();
+        let mut inner = vec![String::new(); geometry.height];
+
+        for cell in row {
+            top.push(corner_char(charset, cell.corner_top_left));
+            top.push_str(&wall_segment(charset, cell.wall_top, geometry.width));
+            top.push(corner_char(charset, cell.corner_top_right));
+
+            let mut inner_text = cell.inner_text.split('\n');
+            for line in &mut inner {
+                line.push(vertical_char(charset, cell.wall_left));
+                line.push_str(inner_text.next().unwrap_or_default());
+                line.push(vertical_char(charset, cell.wall_right));
+            }
+
+            bottom.push(corner_char(charset, cell.corner_bottom_left));
+            bottom.push_str(&wall_segment(charset, cell.wall_bottom, geometry.width));
+            bottom.push(corner_char(charset, cell.corner_bottom_right));
+        }
+
+        lines.push(top);
+        lines.extend(inner);
+        lines.push(bottom);
+    }
+
+    lines.join("\n")
+}
+
+fn cell(maze: &Maze, r: usize, c: usize) -> Option<&Cell> {
+    maze.cells.get(r)?.get(c)
+}
+
+/// Returns the number of cells along boundary line `r`, covering the rows on both sides.
+fn row_len(maze: &Maze, r: usize) -> usize {
+    let above = r
+        .checked_sub(1)
+        .and_then(|r| maze.cells.get(r))
+        .map_or(0, Vec::len);
+    let below = maze.cells.get(r).map_or(0, Vec::len);
+    above.max(below)
+}
+
+/// Returns whether there is a wall above cell `(r, c)`.
+fn horizontal_wall(maze: &Maze, r: usize, c: usize) -> bool {
+    let above = r.checked_sub(1).and_then(|r| cell(maze, r, c));
+    above.is_some_and(|cell| cell.wall_bottom) || cell(maze, r, c).is_some_and(|cell| cell.wall_top)
+}
+
+/// Returns whether there is a wall left of cell `(r, c)`.
+fn vertical_wall(maze: &Maze, r: usize, c: usize) -> bool {
+    let left = c.checked_sub(1).and_then(|c| cell(maze, r, c));
+    left.is_some_and(|cell| cell.wall_right) || cell(maze, r, c).is_some_and(|cell| cell.wall_left)
+}
+
+/// Returns the glyph at the top left corner of cell `(r, c)`.
+fn corner(maze: &Maze, charset: Charset, r: usize, c: usize) -> char {
+    let up = r.checked_sub(1);
+    let left = c.checked_sub(1);
+
+    let present = up
+        .and_then(|r| left.and_then(|c| cell(maze, r, c)))
+        .is_some_and(|cell| cell.corner_bottom_right)
+        || up
+            .and_then(|r| cell(maze, r, c))
+            .is_some_and(|cell| cell.corner_bottom_left)
+        || left
+            .and_then(|c| cell(maze, r, c))
+            .is_some_and(|cell| cell.corner_top_right)
+        || cell(maze, r, c).is_some_and(|cell| cell.corner_top_left);
+
+    if !present {
+        return ' ';
+    }
+
+    charset.corner(Arms {
+        up: up.is_some_and(|r| vertical_wall(maze, r, c)),
+        right: horizontal_wall(maze, r, c),
+        down: vertical_wall(maze, r, c),
+        left: left.is_some_and(|c| horizontal_wall(maze, r, c)),
+    })
+}
+
+fn wall_segment(charset: Charset, wall: bool, width: usize) -> String {
+    let c = if wall { charset.horizontal_wall() } else { ' ' };
+    std::iter::repeat_n(c, width).collect()
+}
+
+/// Returns the glyph for a corner drawn by a single cell, which has no neighbours to join.
+fn corner_char(charset: Charset, present: bool) -> char {
+    if present {
+        charset.corner(Arms::default())
+    } else {
+        ' '
+    }
+}
+
+fn vertical_char(charset: Charset, wall: bool) -> char {
+    if wall {
+        charset.vertical_wall()
+    } else {
+        ' '
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+
+use crate::{
+    maze::Maze,
+    pos::{Direction, Pos},
+};
+
+/// A route through a maze: the cells it visits, and the move made between each pair.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path {
+    /// Every cell along the route, from the start to the goal inclusive.
+    pub positions: Vec<Pos>,
+    /// The move from each position to the next, so one shorter than `positions`.
+    pub directions: Vec<Direction>,
+}
+
+impl Path {
+    /// Returns the number of moves along the path.
+    pub fn len(&self) -> usize {
+        self.directions.len()
+    }
+
+    /// Returns whether the path has no moves, as when the start is also the goal.
+    pub fn is_empty(&self) -> bool {
+        self.directions.is_empty()
+    }
+
+    /// Returns the cell the path starts from.
+    pub fn start(&self) -> Pos {
+        self.positions[0]
+    }
+
+    /// Returns the cell the path ends at.
+    pub fn goal(&self) -> Pos {
+        self.positions[self.positions.len() - 1]
+    }
+}
+
+impl Maze {
+    /// Finds a shortest path from `start` to `goal`, moving only through open passages as
+    /// `can_move` sees them.
+    ///
+    /// Returns `None` if the goal can't be reached, or if either position is outside the maze.
+    pub fn solve(&self, start: Pos, goal: Pos) -> Option<Path> {
+        self.get(start)?;
+        self.get(goal)?;
+
+        // The move that first reached each cell, by row and column
+        let mut came_from = self
+            .cells
+            .iter()
+            .map(|row| vec![None; row.len()])
+            .collect::<Vec<Vec<Option<Direction>>>>();
+        let mut visited = came_from
+            .iter()
+            .map(|row| vec![false; row.len()])
+            .collect::<Vec<_>>();
+
+        let mut queue = VecDeque::from([start]);
+        visited[start.row][start.col] = true;
+
+        while let Some(pos) = queue.pop_front() {
+            if pos == goal {
+                return Some(trace_back(&came_from, start, goal));
+            }
+
+            for (direction, next) in self.open_neighbors(pos) {
+                if !visited[next.row][next.col] {
+                    visited[next.row][next.col] = true;
+                    came_from[next.row][next.col] = Some(direction);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns a copy of the maze with the cells along `path` marked with `*`.
+    ///
+    /// Cells with inner text of their own, such as start and goal labels, are left as they
+    /// are, so the route stays readable around them.
+    pub fn overlay_path(&self, path: &Path) -> Maze {
+        let mut maze = self.clone();
+        for &pos in &path.positions {
+            let Some(cell) = maze
+                .cells
+                .get_mut(pos.row)
+                .and_then(|row| row.get_mut(pos.col))
+            else {
+                continue;
+            };
+            if !cell.inner_text.trim().is_empty() {
+                continue;
+            }
+
+            // Put the mark in the middle of the middle line
+            let geometry = cell.geometry();
+            cell.inner_text = (0..geometry.height)
+                .map(|line| {
+                    let mut text = " ".repeat(geometry.width);
+                    if line == geometry.height / 2 && geometry.width > 0 {
+                        let mid = geometry.width / 2;
+                        text.replace_range(mid..=mid, "*");
+                    }
+                    text
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        maze
+    }
+}
+
+/// Follows the recorded moves back from `goal` to `start`.
+pub(crate) fn trace_back(came_from: &[Vec<Option<Direction>>], start: Pos, goal: Pos) -> Path {
+    let mut positions = vec![goal];
+    let mut directions = Vec::new();
+
+    let mut pos = goal;
+    while pos != start {
+        let direction =
+            came_from[pos.row][pos.col].expect("every visited cell but the start has a move");
+        pos = pos
+            .step(direction.opposite())
+            .expect("moves stay inside the maze");
+        positions.push(pos);
+        directions.push(direction);
+    }
+
+    positions.reverse();
+    directions.reverse();
+    Path {
+        positions,
+        directions,
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::{connectivity::Bfs, maze::Maze, pos::Pos};
+
+/// Measurements of a maze's layout, for level design. See `Maze::stats`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MazeStats {
+    pub width: usize,
+    pub height: usize,
+    pub cells: usize,
+    /// Cells with no open side.
+    pub isolated: usize,
+    /// Cells with exactly one open side.
+    pub dead_ends: usize,
+    /// Cells with two open sides, whether straight or turning.
+    pub corridors: usize,
+    /// Cells with three open sides.
+    pub t_junctions: usize,
+    /// Cells with all four sides open.
+    pub crossroads: usize,
+    /// Open passages between neighbouring cells, each counted once.
+    pub passages: usize,
+    /// Groups of cells reachable from one another.
+    pub components: usize,
+    /// Independent loops, the cyclomatic number `passages - cells + components`. Zero for
+    /// a maze where there is only ever one route between two cells.
+    pub loops: usize,
+    /// The longest shortest path between any two connected cells, in moves. A lower bound
+    /// unless `diameter_exact` is set.
+    pub diameter: usize,
+    /// Whether `diameter` is exact. It always is for mazes without loops; for those with
+    /// loops, `Maze::stats` only estimates it and `Maze::stats_with_diameter` measures it.
+    pub diameter_exact: bool,
+    /// Moves from the start to the nearest goal, or `None` if no goal can be reached.
+    pub solution_length: Option<usize>,
+    /// How hard the solution is to find, or `None` without one. See `Maze::stats`.
+    pub difficulty: Option<f64>,
+}
+
+impl fmt::Display for MazeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "size: {}x{} ({} cells)",
+            self.width, self.height, self.cells
+        )?;
+        writeln!(f, "dead ends: {}", self.dead_ends)?;
+        writeln!(f, "corridors: {}", self.corridors)?;
+        writeln!(f, "t-junctions: {}", self.t_junctions)?;
+        writeln!(f, "crossroads: {}", self.crossroads)?;
+        writeln!(f, "isolated: {}", self.isolated)?;
+        writeln!(f, "passages: {}", self.passages)?;
+        writeln!(f, "components: {}", self.components)?;
+        writeln!(f, "loops: {}", self.loops)?;
+        if self.diameter_exact {
+            writeln!(f, "diameter: {}", self.diameter)?;
+        } else {
+            writeln!(f, "diameter: at least {}", self.diameter)?;
+        }
+        match self.solution_length {
+            Some(len) => writeln!(f, "solution length: {}", len)?,
+            None => writeln!(f, "solution length: unsolvable")?,
+        }
+        match self.difficulty {
+            Some(difficulty) => write!(f, "difficulty: {:.2}", difficulty),
+            None => write!(f, "difficulty: unsolvable"),
+        }
+    }
+}
+
+impl Maze {
+    /// Measures the maze, following open passages as `can_move` sees them.
+    ///
+    /// The solution runs from the start marker to the nearest goal marker, falling back to
+    /// the top left and bottom right cells when the maze has no markers. Its difficulty is
+    /// the path's tortuosity (its length over the straight-line Manhattan distance) scaled
+    /// up by the share of cells that are dead ends, so a direct route through an open room
+    /// scores 1 and winding routes through many false turns score higher.
+    ///
+    /// The diameter takes two breadth-first searches per component. That is exact when the
+    /// maze has no loops, and otherwise a lower bound, flagged by `diameter_exact`.
+    pub fn stats(&self) -> MazeStats {
+        self.measure(false)
+    }
+
+    /// Like `stats`, but measures the exact diameter of mazes with loops by searching from
+    /// every cell, which takes time quadratic in the number of cells.
+    pub fn stats_with_diameter(&self) -> MazeStats {
+        self.measure(true)
+    }
+
+    fn measure(&self, exact_diameter: bool) -> MazeStats {
+        let mut degrees = [0; 5];
+        for (pos, _) in self.iter_row_major() {
+            degrees[self.open_neighbors(pos).count()] += 1;
+        }
+        let passages = self.passage_count();
+
+        let cells = degrees.iter().sum::<usize>();
+        let components = self.component_ids();
+        let component_count = components.iter().flatten().max().map_or(0, |id| id + 1);
+        let loops = (passages + component_count).saturating_sub(cells);
+
+        let start = self.start().unwrap_or_default();
+        let goals = match self.goals() {
+            goals if goals.is_empty() => vec![Pos::new(
+                self.height().saturating_sub(1),
+                self.width().saturating_sub(1),
+            )],
+            goals => goals,
+        };
+        let solution = goals
+            .iter()
+            .filter_map(|&goal| Some((self.solve(start, goal)?.len(), goal)))
+            .min();
+
+        let difficulty = solution.map(|(len, goal)| {
+            let distance = start.row.abs_diff(goal.row) + start.col.abs_diff(goal.col);
+            let tortuosity = len as f64 / distance.max(1) as f64;
+            tortuosity * (1.0 + degrees[1] as f64 / cells.max(1) as f64)
+        });
+
+        MazeStats {
+            width: self.width(),
+            height: self.height(),
+            cells,
+            isolated: degrees[0],
+            dead_ends: degrees[1],
+            corridors: degrees[2],
+            t_junctions: degrees[3],
+            crossroads: degrees[4],
+            passages,
+            components: component_count,
+            loops,
+            diameter: self.diameter(&components, loops > 0 && exact_diameter),
+            diameter_exact: loops == 0 || exact_diameter,
+            solution_length: solution.map(|(len, _)| len),
+            difficulty,
+        }
+    }
+
+    /// Returns the longest shortest path within any component, or with `every_cell` unset
+    /// a lower bound for it.
+    ///
+    /// Without `every_cell`, each component is swept twice: once from its first cell, then
+    /// from the furthest cell that found. Without loops the furthest cell from anywhere is
+    /// one end of a longest path, so this is exact. With `every_cell`, every cell is
+    /// searched from.
+    fn diameter(&self, components: &[Vec<usize>], every_cell: bool) -> usize {
+        let mut searched = vec![false; components.iter().flatten().max().map_or(0, |id| id + 1)];
+        let mut bfs = Bfs::new(self);
+        let mut diameter = 0;
+
+        for (pos, _) in self.iter_row_major() {
+            let id = components[pos.row][pos.col];
+            if !every_cell && searched[id] {
+                continue;
+            }
+            searched[id] = true;
+
+            let from = if every_cell {
+                pos
+            } else {
+                bfs.run(pos).last().map_or(pos, |&(far, _)| far)
+            };
+            let furthest = bfs.run(from).last().map_or(0, |&(_, d)| d);
+            diameter = diameter.max(furthest);
+        }
+        diameter
+    }
+}
@@ -0,0 +1,238 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    cell::Cell,
+    errors::ParseMazeError,
+    maze::Maze,
+    pos::{Corner, Direction, Pos},
+};
+
+/// A disagreement between the walls and corners of a parsed maze.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Inconsistency {
+    /// The cell has a wall on `side`, but its neighbour on that side does not.
+    OneSidedWall { cell: Pos, side: Direction },
+    /// The cell lacks a corner that another cell touching the same point has.
+    MissingCorner { cell: Pos, corner: Corner },
+    /// The cell has a wall on `side` without a corner at one or both of its ends.
+    WallWithoutCorners { cell: Pos, side: Direction },
+}
+
+impl Inconsistency {
+    /// Returns the coordinate of the cell the finding belongs to.
+    pub fn cell(&self) -> Pos {
+        match self {
+            Self::OneSidedWall { cell, .. }
+            | Self::MissingCorner { cell, .. }
+            | Self::WallWithoutCorners { cell, .. } => *cell,
+        }
+    }
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OneSidedWall { cell, side } => write!(
+                f,
+                "cell {}: One-sided wall: The {} wall is missing from the neighbouring cell.",
+                cell, side,
+            ),
+            Self::MissingCorner { cell, corner } => write!(
+                f,
+                "cell {}: Missing corner: The {} corner is present in a neighbouring cell.",
+                cell, corner,
+            ),
+            Self::WallWithoutCorners { cell, side } => write!(
+                f,
+                "cell {}: Wall without corners: The {} wall is missing a corner at its end.",
+                cell, side,
+            ),
+        }
+    }
+}
+
+/// Why a strict parse failed.
+#[derive(Debug)]
+pub enum StrictParseError {
+    /// The maze could not be parsed at all.
+    Parse(Box<ParseMazeError>),
+    /// The maze parsed, but `Maze::validate` found problems with it.
+    Inconsistent(Vec<Inconsistency>),
+}
+
+impl fmt::Display for StrictParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "{}", err),
+            Self::Inconsistent(findings) => {
+                write!(f, "Maze is inconsistent.")?;
+                for finding in findings {
+                    write!(f, " {}", finding)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for StrictParseError {}
+
+impl From<ParseMazeError> for StrictParseError {
+    fn from(err: ParseMazeError) -> Self {
+        Self::Parse(Box::new(err))
+    }
+}
+
+impl Maze {
+    /// Parses a maze as `from_str` does, then rejects it if `validate` finds anything.
+    pub fn parse_strict(s: &str) -> Result<Self, StrictParseError> {
+        let maze = Self::from_str(s)?;
+        let findings = maze.validate();
+        if !findings.is_empty() {
+            return Err(StrictParseError::Inconsistent(findings));
+        }
+        Ok(maze)
+    }
+
+    /// Checks that neighbouring cells agree on the walls and corners they share, and
+    /// that every wall ends in corners. Findings are listed in row-major order.
+    ///
+    /// Mazes parsed from the small format always agree on shared walls, since each is
+    /// drawn once. The large format draws both sides separately, so they can disagree.
+    pub fn validate(&self) -> Vec<Inconsistency> {
+        let mut findings = Vec::new();
+
+        for (r, row) in self.cells.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                let pos = Pos::new(r, c);
+                self.check_walls(pos, cell, &mut findings);
+                self.check_corners(pos, cell, &mut findings);
+                check_wall_ends(pos, cell, &mut findings);
+            }
+        }
+
+        findings
+    }
+
+    fn cell_at(&self, r: Option<usize>, c: Option<usize>) -> Option<&Cell> {
+        self.cells.get(r?)?.get(c?)
+    }
+
+    fn check_walls(&self, pos: Pos, cell: &Cell, findings: &mut Vec<Inconsistency>) {
+        let (r, c) = (pos.row, pos.col);
+        let neighbours = [
+            (
+                Direction::Up,
+                cell.wall_top,
+                self.cell_at(r.checked_sub(1), Some(c))
+                    .map(|n| n.wall_bottom),
+            ),
+            (
+                Direction::Right,
+                cell.wall_right,
+                self.cell_at(Some(r), Some(c + 1)).map(|n| n.wall_left),
+            ),
+            (
+                Direction::Down,
+                cell.wall_bottom,
+                self.cell_at(Some(r + 1), Some(c)).map(|n| n.wall_top),
+            ),
+            (
+                Direction::Left,
+                cell.wall_left,
+                self.cell_at(Some(r), c.checked_sub(1))
+                    .map(|n| n.wall_right),
+            ),
+        ];
+
+        for (side, wall, neighbour_wall) in neighbours {
+            if wall && neighbour_wall == Some(false) {
+                findings.push(Inconsistency::OneSidedWall { cell: pos, side });
+            }
+        }
+    }
+
+    fn check_corners(&self, pos: Pos, cell: &Cell, findings: &mut Vec<Inconsistency>) {
+        let (r, c) = (pos.row, pos.col);
+        let (up, down) = (r.checked_sub(1), Some(r + 1));
+        let (left, right) = (c.checked_sub(1), Some(c + 1));
+        let (row, col) = (Some(r), Some(c));
+
+        // Each corner point is shared with up to three other cells
+        let corners = [
+            (
+                Corner::TopLeft,
+                cell.corner_top_left,
+                [
+                    self.cell_at(up, left).map(|n| n.corner_bottom_right),
+                    self.cell_at(up, col).map(|n| n.corner_bottom_left),
+                    self.cell_at(row, left).map(|n| n.corner_top_right),
+                ],
+            ),
+            (
+                Corner::TopRight,
+                cell.corner_top_right,
+                [
+                    self.cell_at(up, right).map(|n| n.corner_bottom_left),
+                    self.cell_at(up, col).map(|n| n.corner_bottom_right),
+                    self.cell_at(row, right).map(|n| n.corner_top_left),
+                ],
+            ),
+            (
+                Corner::BottomLeft,
+                cell.corner_bottom_left,
+                [
+                    self.cell_at(down, left).map(|n| n.corner_top_right),
+                    self.cell_at(down, col).map(|n| n.corner_top_left),
+                    self.cell_at(row, left).map(|n| n.corner_bottom_right),
+                ],
+            ),
+            (
+                Corner::BottomRight,
+                cell.corner_bottom_right,
+                [
+                    self.cell_at(down, right).map(|n| n.corner_top_left),
+                    self.cell_at(down, col).map(|n| n.corner_top_right),
+                    self.cell_at(row, right).map(|n| n.corner_bottom_left),
+                ],
+            ),
+        ];
+
+        for (corner, present, shared) in corners {
+            if !present && shared.contains(&Some(true)) {
+                findings.push(Inconsistency::MissingCorner { cell: pos, corner });
+            }
+        }
+    }
+}
+
+fn check_wall_ends(pos: Pos, cell: &Cell, findings: &mut Vec<Inconsistency>) {
+    let walls = [
+        (
+            Direction::Up,
+            cell.wall_top,
+            cell.corner_top_left && cell.corner_top_right,
+        ),
+        (
+            Direction::Right,
+            cell.wall_right,
+            cell.corner_top_right && cell.corner_bottom_right,
+        ),
+        (
+            Direction::Down,
+            cell.wall_bottom,
+            cell.corner_bottom_left && cell.corner_bottom_right,
+        ),
+        (
+            Direction::Left,
+            cell.wall_left,
+            cell.corner_top_left && cell.corner_bottom_left,
+        ),
+    ];
+
+    for (side, wall, has_corners) in walls {
+        if wall && !has_corners {
+            findings.push(Inconsistency::WallWithoutCorners { cell: pos, side });
+        }
+    }
+}
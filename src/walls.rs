@@ -0,0 +1,292 @@
+use std::fmt;
+
+use crate::{
+    cell::{Cell, CellGeometry},
+    convert::{ConvertError, Converted, MergePolicy},
+    maze::Maze,
+    pos::{Corner, Direction, Pos},
+};
+
+/// A maze stored as its wall edges and corner points rather than as cells.
+///
+/// Each wall between two cells is stored once, so unlike a `Maze` the two sides of a wall
+/// can't disagree, and changing it changes it for both cells. Corners are stored once per
+/// point for the same reason. A consistent `Maze` converts to a `WallGrid` and back without
+/// losing anything.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WallGrid {
+    width: usize,
+    height: usize,
+    geometry: CellGeometry,
+    /// `height + 1` rows of `width` walls. Row `r` runs along the top of row `r` of cells.
+    horizontal: Vec<bool>,
+    /// `height` rows of `width + 1` walls. Column `c` runs along the left of column `c`.
+    vertical: Vec<bool>,
+    /// `height + 1` rows of `width + 1` corner points. Point `(r, c)` is the top left
+    /// corner of cell `(r, c)`.
+    corners: Vec<bool>,
+    /// The inner text of every cell, in row-major order.
+    labels: Vec<String>,
+}
+
+impl WallGrid {
+    /// Returns a grid of blank cells with the default geometry, each walled in on every side.
+    pub fn new(width: usize, height: usize) -> Self {
+        let geometry = CellGeometry::default();
+        Self {
+            width,
+            height,
+            geometry,
+            horizontal: vec![true; (height + 1) * width],
+            vertical: vec![true; height * (width + 1)],
+            corners: vec![true; (height + 1) * (width + 1)],
+            labels: vec![blank_text(geometry); width * height],
+        }
+    }
+
+    /// Builds the wall grid of `maze`, first merging the two sides of each wall and the up
+    /// to four cells sharing each corner point according to `policy`, as
+    /// `Maze::merge_walls` does. The conflicts it resolved come back with the grid.
+    ///
+    /// Rows shorter than the longest are padded with blank cells that have no walls or
+    /// corners of their own.
+    pub fn from_maze(maze: &Maze, policy: MergePolicy) -> Result<Converted<Self>, ConvertError> {
+        let Converted {
+            value: maze,
+            conflicts,
+        } = maze.merge_walls(policy)?;
+        let cell = |r: Option<usize>, c: Option<usize>| maze.cells.get(r?)?.get(c?);
+
+        let (width, height) = (maze.width(), maze.height());
+        let geometry = maze.geometry();
+        let mut grid = Self {
+            width,
+            height,
+            geometry,
+            horizontal: Vec::with_capacity((height + 1) * width),
+            vertical: Vec::with_capacity(height * (width + 1)),
+            corners: Vec::with_capacity((height + 1) * (width + 1)),
+            labels: Vec::with_capacity(width * height),
+        };
+
+        // The cells now agree, so either side of a wall or corner will do
+        for r in 0..=height {
+            for c in 0..width {
+                grid.horizontal.push(
+                    cell(r.checked_sub(1), Some(c)).is_some_and(|cell| cell.wall_bottom)
+                        || cell(Some(r), Some(c)).is_some_and(|cell| cell.wall_top),
+                );
+            }
+        }
+
+        for r in 0..height {
+            for c in 0..=width {
+                grid.vertical.push(
+                    cell(Some(r), c.checked_sub(1)).is_some_and(|cell| cell.wall_right)
+                        || cell(Some(r), Some(c)).is_some_and(|cell| cell.wall_left),
+                );
+            }
+        }
+
+        for r in 0..=height {
+            for c in 0..=width {
+                let (up, left) = (r.checked_sub(1), c.checked_sub(1));
+                grid.corners.push(
+                    cell(up, left).is_some_and(|cell| cell.corner_bottom_right)
+                        || cell(up, Some(c)).is_some_and(|cell| cell.corner_bottom_left)
+                        || cell(Some(r), left).is_some_and(|cell| cell.corner_top_right)
+                        || cell(Some(r), Some(c)).is_some_and(|cell| cell.corner_top_left),
+                );
+            }
+        }
+
+        let blank = blank_text(geometry);
+        for r in 0..height {
+            for c in 0..width {
+                grid.labels.push(
+                    cell(Some(r), Some(c))
+                        .map_or_else(|| blank.clone(), |cell| cell.inner_text.clone()),
+                );
+            }
+        }
+
+        Ok(Converted {
+            value: grid,
+            conflicts,
+        })
+    }
+
+    /// Expands the grid into a `Maze`, giving both cells beside each wall a copy of it.
+    pub fn to_maze(&self) -> Maze {
+        let cells = (0..self.height)
+            .map(|r| {
+                (0..self.width)
+                    .map(|c| {
+                        let pos = Pos::new(r, c);
+                        Cell {
+                            wall_top: self.horizontal[self.horizontal_index(r, c)],
+                            wall_bottom: self.horizontal[self.horizontal_index(r + 1, c)],
+                            wall_left: self.vertical[self.vertical_index(r, c)],
+                            wall_right: self.vertical[self.vertical_index(r, c + 1)],
+                            corner_top_left: self.has_corner(pos, Corner::TopLeft),
+                            corner_top_right: self.has_corner(pos, Corner::TopRight),
+                            corner_bottom_left: self.has_corner(pos, Corner::BottomLeft),
+                            corner_bottom_right: self.has_corner(pos, Corner::BottomRight),
+                            inner_text: self.labels[r * self.width + c].clone(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Maze { cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn geometry(&self) -> CellGeometry {
+        self.geometry
+    }
+
+    /// Returns whether the cell at `pos` has a wall on `side`. Cells outside the grid have none.
+    pub fn has_wall(&self, pos: Pos, side: Direction) -> bool {
+        self.wall_index(pos, side)
+            .is_some_and(|(horizontal, index)| {
+                if horizontal {
+                    self.horizontal[index]
+                } else {
+                    self.vertical[index]
+                }
+            })
+    }
+
+    /// Adds or removes the wall on `side` of the cell at `pos`, and so on the facing side of
+    /// its neighbour. Adding a wall also adds the corners at its ends. Returns `false`, changing
+    /// nothing, if `pos` is outside the grid.
+    pub fn set_wall(&mut self, pos: Pos, side: Direction, present: bool) -> bool {
+        let Some((horizontal, index)) = self.wall_index(pos, side) else {
+            return false;
+        };
+        if horizontal {
+            self.horizontal[index] = present;
+        } else {
+            self.vertical[index] = present;
+        }
+
+        if present {
+            let ends = match side {
+                Direction::Up => [Corner::TopLeft, Corner::TopRight],
+                Direction::Right => [Corner::TopRight, Corner::BottomRight],
+                Direction::Down => [Corner::BottomLeft, Corner::BottomRight],
+                Direction::Left => [Corner::TopLeft, Corner::BottomLeft],
+            };
+            for corner in ends {
+                let index = self.corner_index(pos, corner);
+                self.corners[index] = true;
+            }
+        }
+        true
+    }
+
+    /// Returns whether the cell at `pos` has `corner`. Cells outside the grid have none.
+    pub fn has_corner(&self, pos: Pos, corner: Corner) -> bool {
+        self.contains(pos) && self.corners[self.corner_index(pos, corner)]
+    }
+
+    /// Returns the inner text of the cell at `pos`, or `None` if it is outside the grid.
+    pub fn label(&self, pos: Pos) -> Option<&str> {
+        self.contains(pos)
+            .then(|| self.labels[pos.row * self.width + pos.col].as_str())
+    }
+
+    /// Replaces the inner text of the cell at `pos`. Returns `false`, changing nothing, if
+    /// `pos` is outside the grid.
+    pub fn set_label(&mut self, pos: Pos, text: impl Into<String>) -> bool {
+        if !self.contains(pos) {
+            return false;
+        }
+        self.labels[pos.row * self.width + pos.col] = text.into();
+        true
+    }
+
+    /// Returns whether there is an open passage from `pos` to its neighbour in `direction`.
+    pub fn can_move(&self, pos: Pos, direction: Direction) -> bool {
+        pos.step(direction)
+            .is_some_and(|next| self.contains(next) && !self.has_wall(pos, direction))
+    }
+
+    /// Returns the neighbours of `pos` that can be moved to, clockwise from `Up`.
+    pub fn open_neighbors(&self, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+        Direction::ALL.into_iter().filter_map(move |direction| {
+            self.can_move(pos, direction)
+                .then(|| pos.step(direction))
+                .flatten()
+                .map(|next| (direction, next))
+        })
+    }
+
+    fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    fn horizontal_index(&self, r: usize, c: usize) -> usize {
+        r * self.width + c
+    }
+
+    fn vertical_index(&self, r: usize, c: usize) -> usize {
+        r * (self.width + 1) + c
+    }
+
+    /// Returns which wall list holds the wall on `side` of `pos` (`true` for horizontal)
+    /// and its index there.
+    fn wall_index(&self, pos: Pos, side: Direction) -> Option<(bool, usize)> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let (r, c) = (pos.row, pos.col);
+        Some(match side {
+            Direction::Up => (true, self.horizontal_index(r, c)),
+            Direction::Down => (true, self.horizontal_index(r + 1, c)),
+            Direction::Left => (false, self.vertical_index(r, c)),
+            Direction::Right => (false, self.vertical_index(r, c + 1)),
+        })
+    }
+
+    fn corner_index(&self, pos: Pos, corner: Corner) -> usize {
+        let (r, c) = match corner {
+            Corner::TopLeft => (pos.row, pos.col),
+            Corner::TopRight => (pos.row, pos.col + 1),
+            Corner::BottomLeft => (pos.row + 1, pos.col),
+            Corner::BottomRight => (pos.row + 1, pos.col + 1),
+        };
+        r * (self.width + 1) + c
+    }
+}
+
+impl Maze {
+    /// Builds the maze's `WallGrid`, as `WallGrid::from_maze` does.
+    pub fn to_wall_grid(&self, policy: MergePolicy) -> Result<Converted<WallGrid>, ConvertError> {
+        WallGrid::from_maze(self, policy)
+    }
+}
+
+impl From<&WallGrid> for Maze {
+    fn from(grid: &WallGrid) -> Self {
+        grid.to_maze()
+    }
+}
+
+impl fmt::Display for WallGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_maze().render_sm())
+    }
+}
+
+fn blank_text(geometry: CellGeometry) -> String {
+    vec![" ".repeat(geometry.width); geometry.height].join("\n")
+}
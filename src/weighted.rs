@@ -0,0 +1,153 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{
+    maze::Maze,
+    pos::{Direction, Pos},
+    solve::{trace_back, Path},
+};
+
+/// How much it costs to enter a cell, read from its inner text.
+///
+/// Texts are trimmed of surrounding whitespace, then read in this order:
+///
+/// - A number, such as `" 5 "`, costs that much when `numeric_weights` is set.
+/// - Text made only of one `terrain` character, such as `"~~~"` or `"#"`, costs what that
+///   character does. A cost of `None` makes the cell impassable.
+/// - Anything else, blank cells and labels included, costs `default_cost`.
+///
+/// The default reads numbers as weights, `~` as water costing 3, `#` as impassable, and
+/// everything else as costing 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CostModel {
+    pub default_cost: u32,
+    pub numeric_weights: bool,
+    pub terrain: Vec<(char, Option<u32>)>,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            default_cost: 1,
+            numeric_weights: true,
+            terrain: vec![('~', Some(3)), ('#', None)],
+        }
+    }
+}
+
+impl CostModel {
+    /// Returns the cost of entering a cell with `inner_text`, or `None` if it is impassable.
+    pub fn cost(&self, inner_text: &str) -> Option<u32> {
+        let text = inner_text.trim();
+
+        if self.numeric_weights && !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) {
+            if let Ok(cost) = text.parse() {
+                return Some(cost);
+            }
+        }
+
+        let mut chars = text.chars();
+        if let Some(first) = chars.next() {
+            if chars.all(|c| c == first) {
+                if let Some(&(_, cost)) = self.terrain.iter().find(|(c, _)| *c == first) {
+                    return cost;
+                }
+            }
+        }
+
+        Some(self.default_cost)
+    }
+}
+
+/// A route through a maze along with what it costs to follow.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WeightedPath {
+    pub path: Path,
+    /// The summed cost of every cell entered along the path. The start is not entered,
+    /// so it costs nothing.
+    pub cost: u64,
+}
+
+impl Maze {
+    /// Returns the cost of entering the cell at `pos` under `model`, or `None` if it is
+    /// impassable or outside the maze.
+    pub fn cost_with(&self, pos: Pos, model: &CostModel) -> Option<u32> {
+        model.cost(&self.get(pos)?.inner_text)
+    }
+
+    /// Finds a minimum-cost path from `start` to `goal` with Dijkstra's algorithm.
+    ///
+    /// Returns `None` if every route to the goal is walled off or passes an impassable cell.
+    pub fn solve_dijkstra(&self, start: Pos, goal: Pos, model: &CostModel) -> Option<WeightedPath> {
+        self.search(start, goal, model, |_| 0)
+    }
+
+    /// Finds a minimum-cost path from `start` to `goal` with A*, guided by the Manhattan
+    /// distance to the goal.
+    ///
+    /// The distance is scaled by the cheapest cell in the maze, so the heuristic never
+    /// overestimates and the path found costs the same as Dijkstra's.
+    pub fn solve_astar(&self, start: Pos, goal: Pos, model: &CostModel) -> Option<WeightedPath> {
+        let cheapest = self
+            .iter_row_major()
+            .filter_map(|(_, cell)| model.cost(&cell.inner_text))
+            .min()
+            .unwrap_or(0);
+
+        self.search(start, goal, model, |pos| {
+            (pos.row.abs_diff(goal.row) + pos.col.abs_diff(goal.col)) as u64 * cheapest as u64
+        })
+    }
+
+    fn search(
+        &self,
+        start: Pos,
+        goal: Pos,
+        model: &CostModel,
+        heuristic: impl Fn(Pos) -> u64,
+    ) -> Option<WeightedPath> {
+        self.get(start)?;
+        self.get(goal)?;
+
+        let mut best = self
+            .cells
+            .iter()
+            .map(|row| vec![u64::MAX; row.len()])
+            .collect::<Vec<_>>();
+        let mut came_from = self
+            .cells
+            .iter()
+            .map(|row| vec![None; row.len()])
+            .collect::<Vec<Vec<Option<Direction>>>>();
+
+        // Ordered by estimated total cost, then by cost so far
+        let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+        best[start.row][start.col] = 0;
+
+        while let Some(Reverse((_, cost, pos))) = queue.pop() {
+            if pos == goal {
+                return Some(WeightedPath {
+                    path: trace_back(&came_from, start, goal),
+                    cost,
+                });
+            }
+            if cost > best[pos.row][pos.col] {
+                // A cheaper route here was already expanded
+                continue;
+            }
+
+            for (direction, next) in self.open_neighbors(pos) {
+                let Some(step) = self.cost_with(next, model) else {
+                    continue;
+                };
+                let next_cost = cost + step as u64;
+                if next_cost < best[next.row][next.col] {
+                    best[next.row][next.col] = next_cost;
+                    came_from[next.row][next.col] = Some(direction);
+                    queue.push(Reverse((next_cost + heuristic(next), next_cost, next)));
+                }
+            }
+        }
+
+        None
+    }
+}
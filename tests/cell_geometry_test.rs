@@ -0,0 +1,98 @@
+use maze_parse::{detect_format, Cell, CellGeometry, Maze};
+
+const WIDE_TALL_SM: &str = "\
++-----+-----+
+| A   |     |
+|     |   B |
++     +-----+
+|  C     D  |
+|           |
++-----+-----+";
+
+const WIDE_TALL_LG: &str = "\
++-----++-----+
+| A   ||     |
+|     ||   B |
++     ++-----+
++     ++-----+
+|  C      D  |
+|            |
++-----++-----+";
+
+#[test]
+fn test_infer_geometry() {
+    let tests = vec![
+        ("+---+\n| A |\n+---+", CellGeometry::new(3, 1)),
+        (WIDE_TALL_SM, CellGeometry::new(5, 2)),
+        (WIDE_TALL_LG, CellGeometry::new(5, 2)),
+        ("┌─┬─┐\n│A│B│\n└─┴─┘", CellGeometry::new(1, 1)),
+        // Without corners on the first line there is nothing to measure
+        (" --- \n| A |\n --- ", CellGeometry::default()),
+    ];
+
+    for (input, expected) in tests {
+        let charset = detect_format(input).format.charset();
+        assert_eq!(CellGeometry::infer(input, charset), expected);
+    }
+}
+
+#[test]
+fn test_parse_multi_line_cell() {
+    let cell = "+----- \n| A    \n|    B \n+      "
+        .parse::<Cell>()
+        .unwrap();
+    assert_eq!(
+        cell,
+        Cell {
+            wall_top: true,
+            wall_bottom: false,
+            wall_left: true,
+            wall_right: false,
+            corner_top_left: true,
+            corner_top_right: false,
+            corner_bottom_left: true,
+            corner_bottom_right: false,
+            inner_text: String::from(" A   \n    B"),
+        },
+    );
+    assert_eq!(cell.geometry(), CellGeometry::new(5, 2));
+
+    assert!("+---+\n+---+".parse::<Cell>().is_err());
+}
+
+#[test]
+fn test_parse_with_geometry() {
+    let geometry = CellGeometry::new(5, 2);
+    let sm = Maze::parse_sm_with(WIDE_TALL_SM, geometry).unwrap();
+    let lg = Maze::parse_lg_with(WIDE_TALL_LG, geometry).unwrap();
+
+    assert_eq!(sm.cells.len(), 2);
+    assert_eq!(sm.cells[0].len(), 2);
+    assert_eq!(sm.cells[0][0].inner_text, " A   \n     ");
+    assert_eq!(sm.cells[0][1].inner_text, "     \n   B ");
+    assert!(!sm.cells[0][0].wall_bottom && sm.cells[0][1].wall_bottom);
+    assert!(!sm.cells[1][0].wall_right);
+    assert_eq!(sm.geometry(), geometry);
+
+    assert_eq!(lg, sm);
+    assert_eq!(WIDE_TALL_SM.parse::<Maze>().unwrap(), sm);
+    assert_eq!(WIDE_TALL_LG.parse::<Maze>().unwrap(), sm);
+}
+
+#[test]
+fn test_render_with_geometry() {
+    let maze = Maze::parse_sm_with(WIDE_TALL_SM, CellGeometry::new(5, 2)).unwrap();
+    let rendered = maze.render_box();
+    assert_eq!(
+        rendered,
+        "\
+┌─────┬─────┐
+│ A   │     │
+│     │   B │
+│     └─────┤
+│  C     D  │
+│           │
+└───────────┘",
+    );
+    assert_eq!(rendered.parse::<Maze>().unwrap(), maze);
+}
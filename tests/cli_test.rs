@@ -0,0 +1,324 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    process::{Command, Output, Stdio},
+};
+
+use maze_parse::{Generator, Maze};
+
+fn run_maze(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_maze"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // The command may exit before reading its input, e.g. on a usage error
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_cli_validate() {
+    let output = run_maze(&["validate", "my_maze_sm", "my_maze_lg"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "my_maze_sm: ok\nmy_maze_lg: ok\n",
+    );
+
+    let output = run_maze(&["validate", "-"], "not a maze");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_parse_json_from_stdin() {
+    let output = run_maze(&["parse", "--format", "json"], "+---+\n| A |\n+---+");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"cells\":[[{\"wall_top\":true,\"wall_bottom\":true,\"wall_left\":true,\"wall_right\":true,\
+\"corner_top_left\":true,\"corner_top_right\":true,\"corner_bottom_left\":true,\"corner_bottom_right\":true,\
+\"inner_text\":\" A \"}]]}\n",
+    );
+}
+
+#[test]
+fn test_cli_exit_codes() {
+    assert_eq!(run_maze(&[], "").status.code(), Some(2));
+    assert_eq!(run_maze(&["bogus"], "").status.code(), Some(2));
+    assert_eq!(
+        run_maze(&["parse", "--format", "xml"], "").status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        run_maze(&["parse", "no_such_file"], "").status.code(),
+        Some(3)
+    );
+    assert_eq!(run_maze(&["--help"], "").status.code(), Some(0));
+}
+
+#[test]
+fn test_cli_explain() {
+    let output = run_maze(&["explain"], "+---+\n| A |\n+---");
+    assert_eq!(output.status.code(), Some(1));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("<stdin>:\nerror: Ragged row"));
+    assert!(stdout.contains("= hint: line 3 is 1 character shorter than line 1"));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "maze: 1 of 1 mazes failed to parse\n"
+    );
+
+    // The formats the maze wasn't detected as are compared in the report
+    let output = run_maze(&["explain"], "+---+---+\n| A | B |\n+---+---+\n| C   D |\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("= hint: large format expects doubled `++` corners here"));
+    assert!(stdout
+        .contains("The small format was the closer match, reading up to line 4 before failing."));
+
+    let output = run_maze(&["explain", "my_maze_sm"], "");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_cli_validate_strict() {
+    let input = "+---++---+\n| A || B |\n+---+ ---+";
+    let output = run_maze(&["validate"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "<stdin>: ok\n");
+    let warnings = String::from_utf8(output.stderr).unwrap();
+    assert!(warnings.starts_with("<stdin>: warning: cell (0, 1): Missing corner"));
+    assert_eq!(warnings.lines().count(), 3);
+
+    let output = run_maze(&["validate", "--strict"], input);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("<stdin>: cell (0, 1): Missing corner"));
+}
+
+#[test]
+fn test_cli_render() {
+    let output = run_maze(&["render"], "+---++---+\n| A || B |\n+---++---+");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "+---+---+\n| A | B |\n+---+---+\n",
+    );
+
+    let output = run_maze(&["parse", "--format", "ascii"], "+---+\n| A |\n+---+");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "+---+\n| A |\n+---+\n"
+    );
+}
+
+#[test]
+fn test_cli_convert() {
+    let input = "+---++---+\n| A  | B |\n+---++---+";
+
+    let output = run_maze(&["convert", "--to", "sm"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "+---+---+\n| A | B |\n+---+---+\n",
+    );
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "<stdin>: warning: cell (0, 1): One-sided wall: The left wall is missing from the \
+neighbouring cell. (merged by union)\n",
+    );
+
+    let output = run_maze(&["convert", "--to", "sm", "--merge", "error"], input);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_maze(
+        &["convert", "--to", "lg"],
+        "+---+---+\n| A   B |\n+---+---+",
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "+---++---+\n| A    B |\n+---++---+\n",
+    );
+
+    assert_eq!(run_maze(&["convert"], input).status.code(), Some(2));
+    assert_eq!(
+        run_maze(&["convert", "--to", "xl"], input).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_cli_solve() {
+    let input = "+---+---+\n|     B |\n+   +---+\n| C     |\n+---+---+";
+
+    let output = run_maze(&["solve"], input);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "+---+---+\n| *   B |\n+   +---+\n| C   * |\n+---+---+\n",
+    );
+
+    let output = run_maze(&["solve", "--start", "1,0", "--goal", "0,1"], input);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_maze(&["solve"], "+---+---+\n| A | B |\n+---+---+");
+    assert_eq!(output.status.code(), Some(1));
+
+    // Endpoints can be labels, and default to the maze's own markers, taking the nearest goal
+    let labelled = "\
++---+---+---+
+| E   S     |
++---+---+   +
+| A       E |
++---+---+---+";
+    let output = run_maze(&["solve"], labelled);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", labelled),
+    );
+    let output = run_maze(&["solve", "--goal", "A"], labelled);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "\
++---+---+---+
+| E   S   * |
++---+---+   +
+| A   *   E |
++---+---+---+
+",
+    );
+    assert_eq!(
+        run_maze(&["solve", "--start", "X"], labelled).status.code(),
+        Some(1)
+    );
+    let output = run_maze(&["solve", "--start", "99,99"], labelled);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "maze: <stdin>: cell (99, 99) is outside the maze\n"
+    );
+}
+
+#[test]
+fn test_cli_stdout_closed_early() {
+    // As with `maze generate --size 300x300 | head -n 1`
+    let mut child = Command::new(env!("CARGO_BIN_EXE_maze"))
+        .args(["generate", "--size", "300x300"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first)
+        .unwrap();
+    assert!(first.starts_with("+---+"));
+
+    let status = child.wait().unwrap();
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .unwrap();
+    assert_eq!(status.code(), Some(0));
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn test_cli_generate() {
+    let output = run_maze(
+        &[
+            "generate",
+            "--size",
+            "5x4",
+            "--algorithm",
+            "kruskal",
+            "--seed",
+            "9",
+        ],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.trim_end(),
+        Maze::generate(5, 4, Generator::Kruskal, 9).to_string()
+    );
+
+    let output = run_maze(&["generate", "--size", "2x2", "--format", "json"], "");
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("{\"cells\":"));
+
+    assert_eq!(
+        run_maze(&["generate", "--size", "big"], "").status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        run_maze(&["generate", "--algorithm", "dfs"], "")
+            .status
+            .code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_cli_stats() {
+    let output = run_maze(&["stats", "my_maze_sm"], "");
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("my_maze_sm:\nsize: 4x4 (16 cells)\ndead ends: 5\n"));
+    assert!(stdout.contains("\nloops: 0\ndiameter: 11\nsolution length: 6\n"));
+
+    let output = run_maze(
+        &["stats", "--format", "json"],
+        "+---+---+\n| S | E |\n+---+---+",
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"name\":\"<stdin>\",\"width\":2,\"height\":1,\"cells\":2,\"isolated\":2,\
+\"dead_ends\":0,\"corridors\":0,\"t_junctions\":0,\"crossroads\":0,\"passages\":0,\
+\"components\":2,\"loops\":0,\"diameter\":0,\"diameter_exact\":true,\"solution_length\":null,\"difficulty\":null}\n",
+    );
+
+    let open = "+---+---+\n|       |\n+   +   +\n|       |\n+---+---+";
+    let output = run_maze(&["stats"], open);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("\nloops: 1\ndiameter: at least 2\n"));
+    let output = run_maze(&["stats", "--exact"], open);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("\nloops: 1\ndiameter: 2\n"));
+}
+
+#[test]
+fn test_cli_check() {
+    let output = run_maze(&["check", "my_maze_sm", "my_maze_lg"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "my_maze_sm: perfect\nmy_maze_lg: perfect\n",
+    );
+
+    let input = "+---+---+---+\n| S     | A |\n+   +   +---+\n|       | B |\n+---+---+---+";
+    let output = run_maze(&["check"], input);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "<stdin>: not perfect: 3 components, 1 loop\n  unreachable from (0, 0): (0, 2), (1, 2)\n",
+    );
+
+    let output = run_maze(&["check", "--start", "A"], input);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .ends_with("  unreachable from (0, 2): (0, 0), (0, 1), (1, 0), (1, 1), (1, 2)\n"));
+}
@@ -0,0 +1,80 @@
+use maze_parse::{Generator, Maze, Pos};
+
+const SPLIT: &str = "\
++---+---+---+
+| S     | A |
++   +   +---+
+|       | B |
++---+---+---+";
+
+#[test]
+fn test_perfect_mazes() {
+    for path in ["my_maze_sm", "my_maze_lg"] {
+        let maze = Maze::new_from_file(path).unwrap();
+        assert!(maze.is_connected());
+        assert!(maze.is_perfect());
+        assert_eq!(maze.unreachable_cells(Pos::new(2, 1)), vec![]);
+        assert_eq!(maze.components().len(), 1);
+        assert_eq!(maze.passage_count(), 15);
+    }
+
+    for generator in Generator::ALL {
+        assert!(
+            Maze::generate(9, 7, generator, 11).is_perfect(),
+            "{}",
+            generator
+        );
+    }
+}
+
+#[test]
+fn test_loops_and_sealed_cells() {
+    let maze = SPLIT.parse::<Maze>().unwrap();
+    assert!(!maze.is_connected());
+    assert!(!maze.is_perfect());
+
+    assert_eq!(
+        maze.components(),
+        vec![
+            vec![
+                Pos::new(0, 0),
+                Pos::new(0, 1),
+                Pos::new(1, 0),
+                Pos::new(1, 1)
+            ],
+            vec![Pos::new(0, 2)],
+            vec![Pos::new(1, 2)],
+        ],
+    );
+    assert_eq!(
+        maze.unreachable_cells(Pos::new(0, 0)),
+        vec![Pos::new(0, 2), Pos::new(1, 2)],
+    );
+    assert_eq!(maze.unreachable_cells(Pos::new(0, 2)).len(), 5);
+    assert_eq!(maze.unreachable_cells(Pos::new(5, 5)).len(), 6);
+    assert_eq!(maze.passage_count(), 4);
+
+    // Connected, but the open room is a loop
+    let room = "+---+---+\n|       |\n+   +   +\n|       |\n+---+---+"
+        .parse::<Maze>()
+        .unwrap();
+    assert!(room.is_connected());
+    assert!(!room.is_perfect());
+    assert_eq!(room.passage_count(), 4);
+
+    // A wall on only one side of a passage still seals it
+    let one_sided = Maze::parse_lg("+---++---+\n| A  | B |\n+---++---+").unwrap();
+    assert!(!one_sided.is_connected());
+}
+
+#[test]
+fn test_trivial_mazes() {
+    let single = "+---+\n|   |\n+---+".parse::<Maze>().unwrap();
+    assert!(single.is_perfect());
+    assert_eq!(single.components(), vec![vec![Pos::new(0, 0)]]);
+
+    let empty = Maze { cells: vec![] };
+    assert!(empty.is_connected());
+    assert!(empty.is_perfect());
+    assert_eq!(empty.components(), Vec::<Vec<Pos>>::new());
+}
@@ -0,0 +1,117 @@
+use maze_parse::{Converted, Corner, Direction, Inconsistency, Maze, MazeFormat, MergePolicy, Pos};
+
+const LG_ONE_SIDED: &str = "\
++---++---+
+| A  | B |
++---++   +
++---++---+
+| C || D |
++---++---+";
+
+#[test]
+fn test_convert_between_formats() {
+    let sm = include_str!("../my_maze_sm").trim_end_matches('\n');
+    let lg = include_str!("../my_maze_lg").trim_end_matches('\n');
+
+    for (from, input) in [(MazeFormat::Small, sm), (MazeFormat::Large, lg)] {
+        let maze = Maze::parse_as(input, from, Default::default()).unwrap();
+        for to in [MazeFormat::Small, MazeFormat::Large, MazeFormat::BoxDrawing] {
+            let converted = maze.convert(to, MergePolicy::Error).unwrap();
+            assert!(converted.conflicts.is_empty());
+            let reparsed = Maze::parse_as(&converted.value, to, Default::default()).unwrap();
+            assert_eq!(reparsed, maze, "{} to {}", from, to);
+        }
+    }
+}
+
+#[test]
+fn test_convert_merge_policies() {
+    let maze = Maze::parse_lg(LG_ONE_SIDED).unwrap();
+
+    // Nothing is lost when converting to the large format
+    assert_eq!(
+        maze.convert(MazeFormat::Large, MergePolicy::Error).unwrap(),
+        Converted {
+            value: String::from(LG_ONE_SIDED),
+            conflicts: vec![],
+        }
+    );
+
+    // Both merging policies report what they resolved
+    let conflicts = vec![
+        Inconsistency::OneSidedWall {
+            cell: Pos::new(0, 1),
+            side: Direction::Left,
+        },
+        Inconsistency::OneSidedWall {
+            cell: Pos::new(1, 1),
+            side: Direction::Up,
+        },
+    ];
+
+    let union = maze.convert(MazeFormat::Small, MergePolicy::Union).unwrap();
+    assert_eq!(union.conflicts, conflicts);
+    assert_eq!(
+        union.value,
+        "\
++---+---+
+| A | B |
++---+---+
+| C | D |
++---+---+",
+    );
+    let intersection = maze
+        .convert(MazeFormat::Small, MergePolicy::Intersection)
+        .unwrap();
+    assert_eq!(intersection.conflicts, conflicts);
+    assert_eq!(
+        intersection.value,
+        "\
++---+---+
+| A   B |
++---+   +
+| C | D |
++---+---+",
+    );
+
+    let err = maze
+        .convert(MazeFormat::Small, MergePolicy::Error)
+        .unwrap_err();
+    assert_eq!(err.conflicts(), conflicts);
+
+    // Corners missing from some of the cells sharing them are conflicts too
+    let mut maze = Maze::parse_sm("+---+---+\n| A | B |\n+---+---+").unwrap();
+    maze.cells[0][1].corner_top_left = false;
+    let missing = vec![Inconsistency::MissingCorner {
+        cell: Pos::new(0, 1),
+        corner: Corner::TopLeft,
+    }];
+
+    let union = maze.convert(MazeFormat::Small, MergePolicy::Union).unwrap();
+    assert_eq!(union.conflicts, missing);
+    assert_eq!(union.value, "+---+---+\n| A | B |\n+---+---+");
+    let intersection = maze
+        .convert(MazeFormat::Small, MergePolicy::Intersection)
+        .unwrap();
+    assert_eq!(intersection.conflicts, missing);
+    assert_eq!(intersection.value, "+--- ---+\n| A | B |\n+---+---+");
+    assert_eq!(
+        maze.convert(MazeFormat::Small, MergePolicy::Error)
+            .unwrap_err()
+            .conflicts(),
+        missing
+    );
+}
+
+#[test]
+fn test_merge_walls() {
+    let maze = Maze::parse_lg(LG_ONE_SIDED).unwrap();
+
+    for policy in [MergePolicy::Union, MergePolicy::Intersection] {
+        let merged = maze.merge_walls(policy).unwrap().value;
+        assert_eq!(merged.validate(), vec![], "{}", policy);
+    }
+
+    let sm = Maze::parse_sm("+---+---+\n| A   B |\n+---+---+").unwrap();
+    assert_eq!(sm.merge_walls(MergePolicy::Error).unwrap().value, sm);
+}
@@ -0,0 +1,87 @@
+use maze_parse::{explain, Maze};
+
+#[test]
+fn test_explain_report() {
+    let source = "+---+---+\n| A | B |\n+---+---+\n| C   D |\n";
+    let err = source.parse::<Maze>().unwrap_err();
+
+    assert_eq!(
+        explain(source, &err),
+        "\
+error: Truncated cell: Cell is cut off.
+ --> small format, line 4, columns 1-5, cell (1, 0)
+  |
+3 | +---+---+
+4 | | C   D |
+  | ^^^^^
+  = hint: the maze ends before this row of cells does; check for missing lines
+
+error: Truncated cell: Cell is cut off.
+ --> large format, line 1, columns 6-10, cell (0, 1)
+  |
+1 | +---+---+
+  |      ^^^^^
+  = hint: large format expects doubled `++` corners here
+
+The small format was the closer match, reading up to line 4 before failing.
+
+note: detected the small format (100% confidence: 2 shared corners, 1 shared line of corners)
+",
+    );
+    assert_eq!(err.explain(source), explain(source, &err));
+}
+
+#[test]
+fn test_explain_closer_match() {
+    // Only the detected large format is parsed, but the report compares it with the small one
+    let source = "+---++---+\n| A || B |\n+---++---+\n+---++---+\n| C || D |";
+    let err = source.parse::<Maze>().unwrap_err();
+    assert!(err.sm_err.is_none() && err.lg_err.is_some());
+
+    let report = explain(source, &err);
+    assert!(report.contains(" --> small format, line 1, column 9, cell (0, 1)"));
+    assert!(report.contains(" --> large format, line 5, columns 1-5, cell (1, 0)"));
+    assert!(report
+        .contains("The large format was the closer match, reading up to line 5 before failing."));
+
+    // Box-drawing mazes have no other format to compare with
+    let source = "┌───┬───┐\n│ A │ B │\n└───┴── ┘";
+    let report = explain(source, &source.parse::<Maze>().unwrap_err());
+    assert!(!report.contains("closer match") && !report.contains("small format"));
+}
+
+#[test]
+fn test_explain_hints() {
+    let tests = vec![
+        (
+            "+---+---+\n| A | B |\n+---+---",
+            "hint: line 3 is 1 character shorter than line 1",
+        ),
+        (
+            "+---+---+\n| A | B |\n+- -+---+",
+            "hint: the bottom wall mixes wall characters and spaces",
+        ),
+        (
+            "+---+---+\n| A = B |\n+---+---+",
+            "hint: walls are drawn with `-` and `|` or left blank",
+        ),
+        (
+            "+---+---+\n| A | B |\n+---+---+\n+---+---+\n| C | D |\n+---+---+",
+            "hint: corners are drawn with `+` or left blank",
+        ),
+        (
+            "┌───┬───┐\n│ A │ B │\n└───┴── ┘",
+            "--> box-drawing format, line 3, columns 6-8, cell (0, 1)",
+        ),
+    ];
+
+    for (source, expected) in tests {
+        let report = explain(source, &source.parse::<Maze>().unwrap_err());
+        assert!(
+            report.contains(expected),
+            "expected {:?} in report:\n{}",
+            expected,
+            report,
+        );
+    }
+}
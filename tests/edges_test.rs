@@ -0,0 +1,28 @@
+use maze_parse::{EdgeListError, Maze};
+
+#[test]
+fn test_edge_list_round_trip() {
+    for path in ["my_maze_sm", "my_maze_lg", "my_maze_sm_weighted"] {
+        let maze = Maze::new_from_file(path).unwrap();
+        assert_eq!(Maze::from_edge_list(&maze.to_edge_list()).unwrap(), maze);
+    }
+
+    // One-sided walls become walls on both sides
+    let one_sided = Maze::parse_lg("+---++---+\n| A  | B |\n+---++---+").unwrap();
+    let read_back = Maze::from_edge_list(&one_sided.to_edge_list()).unwrap();
+    assert_eq!(read_back.render_sm(), "+---+---+\n| A | B |\n+---+---+");
+
+    let mut edges = one_sided.to_edge_list();
+    edges.version = 2;
+    assert_eq!(
+        Maze::from_edge_list(&edges),
+        Err(EdgeListError::UnsupportedVersion(2))
+    );
+
+    let mut edges = one_sided.to_edge_list();
+    edges.vertical_walls.push([0, 3]);
+    assert_eq!(
+        Maze::from_edge_list(&edges),
+        Err(EdgeListError::WallOutOfBounds([0, 3]))
+    );
+}
@@ -0,0 +1,191 @@
+use maze_parse::{Corner, Direction, EditError, Generator, Maze, Pos};
+
+const MAZE: &str = "\
++---+---+---+
+| A   B | C |
++   +---+   +
+| D   E   F |
++---+---+---+";
+
+#[test]
+fn test_set_wall_keeps_neighbours_consistent() {
+    let mut maze = MAZE.parse::<Maze>().unwrap();
+
+    maze.set_wall(Pos::new(0, 0), Direction::Right, true)
+        .unwrap();
+    assert!(maze[Pos::new(0, 1)].wall_left);
+    maze.set_wall(Pos::new(1, 1), Direction::Up, false).unwrap();
+    assert!(!maze[Pos::new(0, 1)].wall_bottom);
+    maze.carve(Pos::new(0, 1), Direction::Right).unwrap();
+
+    assert_eq!(
+        maze.render_sm(),
+        "\
++---+---+---+
+| A | B   C |
++   +   +   +
+| D   E   F |
++---+---+---+",
+    );
+    assert!(maze.validate().is_empty());
+
+    assert_eq!(
+        maze.carve(Pos::new(0, 2), Direction::Right),
+        Err(EditError::NoNeighbour {
+            pos: Pos::new(0, 2),
+            direction: Direction::Right,
+        })
+    );
+    assert_eq!(
+        maze.set_wall(Pos::new(2, 0), Direction::Up, true),
+        Err(EditError::OutOfBounds(Pos::new(2, 0)))
+    );
+
+    // Openings in the outer wall go through `set_wall`
+    maze.set_wall(Pos::new(1, 2), Direction::Right, false)
+        .unwrap();
+    assert!(!maze[Pos::new(1, 2)].wall_right);
+}
+
+#[test]
+fn test_set_wall_adds_corners() {
+    let mut maze = Maze::parse_sm("+---+---+\n|       |\n+       +\n|       |\n+---+---+").unwrap();
+    assert!(!maze[Pos::new(0, 0)].corner_bottom_right);
+
+    maze.set_wall(Pos::new(0, 0), Direction::Down, true)
+        .unwrap();
+    for (pos, corner) in [
+        (Pos::new(0, 0), Corner::BottomRight),
+        (Pos::new(0, 1), Corner::BottomLeft),
+        (Pos::new(1, 0), Corner::TopRight),
+        (Pos::new(1, 1), Corner::TopLeft),
+    ] {
+        assert!(maze[pos].has_corner(corner), "{} {}", pos, corner);
+    }
+    assert_eq!(
+        maze.render_sm(),
+        "+---+---+\n|       |\n+---+   +\n|       |\n+---+---+"
+    );
+    assert!(maze.validate().is_empty());
+}
+
+#[test]
+fn test_set_label() {
+    let mut maze = MAZE.parse::<Maze>().unwrap();
+
+    maze.set_label(Pos::new(0, 0), "S").unwrap();
+    maze.set_label(Pos::new(0, 1), "").unwrap();
+    maze.set_label(Pos::new(0, 2), "東").unwrap();
+    assert_eq!(maze[Pos::new(0, 0)].inner_text, " S ");
+    assert_eq!(maze[Pos::new(0, 1)].inner_text, "   ");
+    assert_eq!(maze[Pos::new(0, 2)].inner_text, "東 ");
+    assert_eq!(
+        maze.set_label(Pos::new(1, 0), "Goal"),
+        Err(EditError::LabelDoesNotFit {
+            pos: Pos::new(1, 0),
+            label: String::from("Goal"),
+        })
+    );
+    assert!(maze.set_label(Pos::new(1, 0), "A\nB").is_err());
+
+    let mut tall = "+-----+\n|     |\n|     |\n|     |\n+-----+"
+        .parse::<Maze>()
+        .unwrap();
+    tall.set_label(Pos::new(0, 0), "A\nBB").unwrap();
+    assert_eq!(tall[Pos::new(0, 0)].inner_text, "  A  \n BB  \n     ");
+}
+
+#[test]
+fn test_insert_rows_and_cols() {
+    let mut maze = MAZE.parse::<Maze>().unwrap();
+
+    // The passage from A down to D is stretched through the new row
+    maze.insert_row(1).unwrap();
+    assert_eq!(
+        maze.render_sm(),
+        "\
++---+---+---+
+| A   B | C |
++   +---+   +
+|   |   |   |
++   +---+   +
+| D   E   F |
++---+---+---+",
+    );
+    assert!(maze.validate().is_empty());
+
+    maze.insert_col(3).unwrap();
+    assert_eq!(
+        maze.render_sm(),
+        "\
++---+---+---+---+
+| A   B | C |   |
++   +---+   +---+
+|   |   |   |   |
++   +---+   +---+
+| D   E   F |   |
++---+---+---+---+",
+    );
+    assert!(maze.validate().is_empty());
+
+    assert_eq!(
+        maze.insert_row(5),
+        Err(EditError::RowOutOfBounds {
+            index: 5,
+            height: 3,
+        })
+    );
+    assert_eq!(
+        maze.insert_col(5),
+        Err(EditError::ColOutOfBounds { index: 5, width: 4 })
+    );
+}
+
+#[test]
+fn test_remove_rows_and_cols() {
+    let mut maze = MAZE.parse::<Maze>().unwrap();
+
+    // B and E were walled apart, C and F joined through the removed column
+    maze.remove_col(1).unwrap();
+    assert_eq!(
+        maze.render_sm(),
+        "\
++---+---+
+| A | C |
++   +   +
+| D   F |
++---+---+",
+    );
+    assert!(maze.validate().is_empty());
+
+    // The top row's outer walls move to the row below
+    maze.remove_row(0).unwrap();
+    assert_eq!(maze.render_sm(), "+---+---+\n| D   F |\n+---+---+");
+    assert_eq!(
+        maze.remove_row(1),
+        Err(EditError::RowOutOfBounds {
+            index: 1,
+            height: 1,
+        })
+    );
+}
+
+#[test]
+fn test_insert_then_remove_round_trips() {
+    let maze = Maze::generate(8, 6, Generator::Prim, 11);
+
+    for index in 0..=maze.height() {
+        let mut edited = maze.clone();
+        edited.insert_row(index).unwrap();
+        assert!(edited.validate().is_empty());
+        edited.remove_row(index).unwrap();
+        assert_eq!(edited, maze, "row {}", index);
+    }
+    for index in 0..=maze.width() {
+        let mut edited = maze.clone();
+        edited.insert_col(index).unwrap();
+        assert!(edited.validate().is_empty());
+        edited.remove_col(index).unwrap();
+        assert_eq!(edited, maze, "col {}", index);
+    }
+}
@@ -0,0 +1,72 @@
+use std::fs;
+
+use maze_parse::{detect_format, Evidence, Maze, MazeFormat};
+
+#[test]
+fn test_detect_format() {
+    let tests = vec![
+        (fs::read_to_string("my_maze_sm").unwrap(), MazeFormat::Small),
+        (fs::read_to_string("my_maze_lg").unwrap(), MazeFormat::Large),
+        (
+            String::from("┌───┬───┐\n│ A │ B │\n└───┴───┘"),
+            MazeFormat::BoxDrawing,
+        ),
+        (
+            String::from("+---+---+\n| A | B |\n+---+---+"),
+            MazeFormat::Small,
+        ),
+        (
+            String::from("+---++---+\n| A || B |\n+---++---+"),
+            MazeFormat::Large,
+        ),
+    ];
+
+    for (input, expected) in tests {
+        let detection = detect_format(&input);
+        assert_eq!(detection.format, expected, "{}", input);
+        assert!(detection.confidence > 0.5, "{}", detection);
+    }
+}
+
+#[test]
+fn test_detect_format_evidence() {
+    let detection =
+        detect_format("+---++---+\n| A || B |\n+---++---+\n+---++---+\n| C || D |\n+---++---+");
+    assert_eq!(detection.format, MazeFormat::Large);
+    assert_eq!(detection.confidence, 1.0);
+    assert_eq!(
+        detection.evidence,
+        vec![
+            Evidence::DoubledCorners(4),
+            Evidence::DoubledWalls(2),
+            Evidence::DoubledBoundaryLines(1),
+        ],
+    );
+    assert_eq!(
+        detection.to_string(),
+        "large format (100% confidence: 4 doubled `++` corners, 2 doubled `||` walls, \
+1 doubled line of corners)",
+    );
+
+    // A single cell looks the same in both formats
+    let detection = detect_format("+---+\n| A |\n+---+");
+    assert_eq!(detection.format, MazeFormat::Small);
+    assert_eq!(detection.confidence, 0.5);
+    assert!(detection.evidence.is_empty());
+}
+
+#[test]
+fn test_parse_uses_detected_format() {
+    let input = "+---++---+\n| A || B |\n+---++---+";
+    assert_eq!(
+        input.parse::<Maze>().unwrap(),
+        Maze::parse_lg(input).unwrap(),
+    );
+
+    // Only the detected format is attempted
+    let err = "+---++---+\n| A || B |\n+---++- -+"
+        .parse::<Maze>()
+        .unwrap_err();
+    assert!(err.sm_err.is_none());
+    assert!(err.err(MazeFormat::Large).is_some());
+}
@@ -0,0 +1,76 @@
+use maze_parse::{Direction, Generator, Maze, Pos};
+
+/// Counts the passages between cells, each once.
+fn passages(maze: &Maze) -> usize {
+    maze.iter_row_major()
+        .map(|(pos, _)| {
+            [Direction::Right, Direction::Down]
+                .into_iter()
+                .filter(|&direction| maze.can_move(pos, direction))
+                .count()
+        })
+        .sum()
+}
+
+#[test]
+fn test_generate_perfect_mazes() {
+    for generator in Generator::ALL {
+        for (width, height) in [(1, 1), (1, 5), (6, 1), (5, 4), (12, 9)] {
+            let maze = Maze::generate(width, height, generator, 42);
+            assert_eq!((maze.width(), maze.height()), (width, height));
+
+            // A spanning tree: connected, with one fewer passage than cells
+            assert_eq!(passages(&maze), width * height - 1, "{}", generator);
+            for (pos, _) in maze.iter_row_major() {
+                assert!(maze.solve(Pos::new(0, 0), pos).is_some(), "{}", generator);
+            }
+
+            assert_eq!(maze.validate(), vec![], "{}", generator);
+            assert_eq!(Maze::parse_sm(maze.render_sm()).unwrap(), maze);
+        }
+    }
+}
+
+#[test]
+fn test_generate_is_deterministic() {
+    for generator in Generator::ALL {
+        let maze = Maze::generate(10, 10, generator, 7);
+        assert_eq!(Maze::generate(10, 10, generator, 7), maze);
+        assert_ne!(Maze::generate(10, 10, generator, 8), maze, "{}", generator);
+    }
+
+    // Pinned, so changes to the generators or the random numbers behind them are noticed
+    assert_eq!(
+        Maze::generate(4, 3, Generator::RecursiveBacktracker, 1).to_string(),
+        "\
++---+---+---+---+
+|               |
++   +---+---+   +
+|       |       |
++---+---+   +---+
+|               |
++---+---+---+---+",
+    );
+}
+
+#[test]
+fn test_binary_tree_bias() {
+    let maze = Maze::generate(8, 8, Generator::BinaryTree, 3);
+    for col in 0..7 {
+        assert!(maze.can_move(Pos::new(0, col), Direction::Right));
+    }
+    for row in 0..7 {
+        assert!(maze.can_move(Pos::new(row, 0), Direction::Down));
+    }
+}
+
+#[test]
+fn test_generate_empty_and_names() {
+    assert_eq!(Maze::generate(0, 5, Generator::Prim, 1).height(), 0);
+    assert_eq!(Maze::generate(5, 0, Generator::Prim, 1).width(), 0);
+
+    for generator in Generator::ALL {
+        assert_eq!(Generator::from_name(generator.name()), Some(generator));
+    }
+    assert_eq!(Generator::from_name("nope"), None);
+}
@@ -0,0 +1,101 @@
+use maze_parse::{Direction, Maze, Pos};
+
+const MAZE: &str = "\
++---+---+---+
+| A   B | C |
++   +---+   +
+| D   E   F |
++---+---+---+";
+
+#[test]
+fn test_dimensions_and_indexing() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+    assert_eq!(maze.width(), 3);
+    assert_eq!(maze.height(), 2);
+
+    assert_eq!(maze.get(Pos::new(1, 2)).unwrap().inner_text, " F ");
+    assert_eq!(maze[Pos::new(0, 1)].inner_text, " B ");
+    assert_eq!(maze.get(Pos::new(2, 0)), None);
+    assert_eq!(maze.get(Pos::new(0, 3)), None);
+}
+
+#[test]
+fn test_neighbors() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+
+    assert_eq!(
+        maze.neighbors(Pos::new(0, 0)).collect::<Vec<_>>(),
+        vec![
+            (Direction::Right, Pos::new(0, 1)),
+            (Direction::Down, Pos::new(1, 0)),
+        ],
+    );
+    assert_eq!(maze.neighbors(Pos::new(1, 1)).count(), 3);
+
+    assert_eq!(
+        maze.open_neighbors(Pos::new(0, 0)).collect::<Vec<_>>(),
+        vec![
+            (Direction::Right, Pos::new(0, 1)),
+            (Direction::Down, Pos::new(1, 0)),
+        ],
+    );
+    assert_eq!(
+        maze.open_neighbors(Pos::new(1, 1)).collect::<Vec<_>>(),
+        vec![
+            (Direction::Right, Pos::new(1, 2)),
+            (Direction::Left, Pos::new(1, 0)),
+        ],
+    );
+    assert_eq!(
+        maze.open_neighbors(Pos::new(0, 2)).collect::<Vec<_>>(),
+        vec![(Direction::Down, Pos::new(1, 2))],
+    );
+}
+
+#[test]
+fn test_can_move() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+    assert!(maze.can_move(Pos::new(0, 0), Direction::Right));
+    assert!(!maze.can_move(Pos::new(0, 1), Direction::Right));
+    assert!(!maze.can_move(Pos::new(0, 0), Direction::Up));
+    assert!(!maze.can_move(Pos::new(0, 0), Direction::Left));
+    assert!(!maze.can_move(Pos::new(5, 5), Direction::Up));
+
+    // A wall drawn by only one of the two cells still blocks the passage, from both sides
+    let maze = Maze::parse_lg("+---++---+\n| A  | B |\n+---++---+").unwrap();
+    assert!(!maze.can_move(Pos::new(0, 0), Direction::Right));
+    assert!(!maze.can_move(Pos::new(0, 1), Direction::Left));
+}
+
+#[test]
+fn test_iterators() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+    let text = |(_, cell): (Pos, &maze_parse::Cell)| cell.inner_text.trim().to_owned();
+
+    assert_eq!(
+        maze.iter_row_major().map(text).collect::<Vec<_>>(),
+        ["A", "B", "C", "D", "E", "F"],
+    );
+    assert_eq!(
+        maze.iter_col_major().map(text).collect::<Vec<_>>(),
+        ["A", "D", "B", "E", "C", "F"],
+    );
+    assert_eq!(
+        maze.iter_col_major().map(|(pos, _)| pos).nth(1),
+        Some(Pos::new(1, 0))
+    );
+}
+
+#[test]
+fn test_direction() {
+    for direction in Direction::ALL {
+        assert_eq!(direction.opposite().opposite(), direction);
+        let pos = Pos::new(1, 1);
+        assert_eq!(
+            pos.step(direction).unwrap().step(direction.opposite()),
+            Some(pos)
+        );
+    }
+    assert_eq!(Pos::new(0, 0).step(Direction::Up), None);
+    assert_eq!(Pos::new(0, 0).step(Direction::Left), None);
+}
@@ -0,0 +1,162 @@
+use maze_parse::{Direction, Edit, EditError, EditLog, Generator, Maze, Pos};
+
+fn edits() -> Vec<Edit> {
+    vec![
+        Edit::Carve {
+            pos: Pos::new(1, 1),
+            direction: Direction::Right,
+        },
+        Edit::SetWall {
+            pos: Pos::new(2, 2),
+            side: Direction::Down,
+            present: true,
+        },
+        Edit::SetWall {
+            pos: Pos::new(0, 0),
+            side: Direction::Left,
+            present: false,
+        },
+        Edit::SetLabel {
+            pos: Pos::new(0, 0),
+            label: String::from("S"),
+        },
+        Edit::InsertRow(2),
+        Edit::InsertCol(0),
+        Edit::RemoveRow(0),
+        Edit::RemoveCol(3),
+        Edit::InsertRow(3),
+        Edit::RemoveCol(2),
+    ]
+}
+
+#[test]
+fn test_undo_and_redo_restore_each_state() {
+    let mazes = [
+        Maze::generate(5, 4, Generator::Eller, 9),
+        Maze::parse_lg(
+            "\
++---++---++---+
+| A  | B || C |
++---++   ++---+
++---++---++---+
+| D || E    F |
++---++---++---+
++---++---++---+
+| G || H || I |
++---++---++---+",
+        )
+        .unwrap(),
+    ];
+
+    for original in mazes {
+        let mut maze = original.clone();
+        let mut log = EditLog::new();
+        let mut states = vec![maze.clone()];
+        for edit in edits() {
+            log.apply(&mut maze, edit).unwrap();
+            states.push(maze.clone());
+        }
+
+        for state in states.iter().rev().skip(1) {
+            assert!(log.undo(&mut maze).is_some());
+            assert_eq!(&maze, state);
+        }
+        assert_eq!(log.undo(&mut maze), None);
+        assert_eq!(maze, original);
+
+        for state in states.iter().skip(1) {
+            assert!(log.redo(&mut maze).unwrap().is_some());
+            assert_eq!(&maze, state);
+        }
+        assert_eq!(log.redo(&mut maze), Ok(None));
+
+        // Replaying the log on the original rebuilds the edited maze
+        let mut replayed = original.clone();
+        log.replay(&mut replayed).unwrap();
+        assert_eq!(replayed, maze);
+    }
+}
+
+#[test]
+fn test_transactions() {
+    let original = Maze::generate(4, 4, Generator::BinaryTree, 1);
+    let mut maze = original.clone();
+    let mut log = EditLog::new();
+
+    log.begin("add a room");
+    log.apply(&mut maze, Edit::InsertRow(4)).unwrap();
+    log.begin("label it");
+    log.apply(
+        &mut maze,
+        Edit::SetLabel {
+            pos: Pos::new(4, 0),
+            label: String::from("R"),
+        },
+    )
+    .unwrap();
+    log.commit();
+    log.apply(
+        &mut maze,
+        Edit::Carve {
+            pos: Pos::new(3, 0),
+            direction: Direction::Down,
+        },
+    )
+    .unwrap();
+    log.commit();
+    let edited = maze.clone();
+
+    log.apply(&mut maze, Edit::RemoveCol(0)).unwrap();
+    assert_eq!(log.transactions().len(), 2);
+    assert_eq!(log.transactions()[0].name(), "add a room");
+    assert_eq!(log.transactions()[0].edits().count(), 3);
+    assert_eq!(log.undo_name(), Some("remove column 0"));
+
+    assert_eq!(log.undo(&mut maze), Some("remove column 0"));
+    assert_eq!(maze, edited);
+    assert_eq!(log.undo(&mut maze), Some("add a room"));
+    assert_eq!(maze, original);
+    assert_eq!(log.redo_name(), Some("add a room"));
+
+    // A new edit discards the redo history
+    log.apply(
+        &mut maze,
+        Edit::SetWall {
+            pos: Pos::new(0, 0),
+            side: Direction::Up,
+            present: false,
+        },
+    )
+    .unwrap();
+    assert_eq!(log.redo_name(), None);
+    assert_eq!(log.undo_name(), Some("remove the top wall of (0, 0)"));
+}
+
+#[test]
+fn test_rollback_and_refused_edits() {
+    let original = Maze::generate(3, 3, Generator::Kruskal, 4);
+    let mut maze = original.clone();
+    let mut log = EditLog::new();
+
+    log.begin("abandoned");
+    log.apply(&mut maze, Edit::InsertCol(1)).unwrap();
+    log.apply(&mut maze, Edit::RemoveRow(0)).unwrap();
+    log.rollback(&mut maze);
+    assert_eq!(maze, original);
+    assert_eq!(log.undo_name(), None);
+
+    assert_eq!(
+        log.apply(&mut maze, Edit::RemoveRow(3)),
+        Err(EditError::RowOutOfBounds {
+            index: 3,
+            height: 3,
+        })
+    );
+    assert_eq!(maze, original);
+    assert!(log.transactions().is_empty());
+
+    // Empty transactions aren't recorded
+    log.begin("nothing");
+    log.commit();
+    assert_eq!(log.undo(&mut maze), None);
+}
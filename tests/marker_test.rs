@@ -0,0 +1,81 @@
+use maze_parse::{Marker, MarkerScheme, Maze, Pos};
+
+const MAZE: &str = "\
++---+---+---+
+| S   2 | A |
++   +---+   +
+| 1   # | G |
++   +---+   +
+| E       E |
++---+---+---+";
+
+#[test]
+fn test_classify() {
+    let scheme = MarkerScheme::default();
+    assert_eq!(scheme.classify(" S "), Some(Marker::Start));
+    assert_eq!(scheme.classify(" E "), Some(Marker::Goal));
+    assert_eq!(scheme.classify("G"), Some(Marker::Goal));
+    assert_eq!(scheme.classify(" # "), Some(Marker::Blocked));
+    assert_eq!(scheme.classify(" 12"), Some(Marker::Waypoint(12)));
+    assert_eq!(
+        scheme.classify(" A "),
+        Some(Marker::Label(String::from("A")))
+    );
+    assert_eq!(
+        scheme.classify("-1 "),
+        Some(Marker::Label(String::from("-1")))
+    );
+    assert_eq!(scheme.classify("   "), None);
+    assert_eq!(scheme.classify("   \n   "), None);
+}
+
+#[test]
+fn test_default_markers() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+
+    assert_eq!(maze.start(), Some(Pos::new(0, 0)));
+    assert_eq!(
+        maze.goals(),
+        vec![Pos::new(1, 2), Pos::new(2, 0), Pos::new(2, 2)]
+    );
+    assert_eq!(
+        maze.waypoints(),
+        vec![(1, Pos::new(1, 0)), (2, Pos::new(0, 1))]
+    );
+    assert_eq!(
+        maze.markers_with(&MarkerScheme::default())
+            .find(|(_, marker)| *marker == Marker::Blocked),
+        Some((Pos::new(1, 1), Marker::Blocked)),
+    );
+
+    assert_eq!(maze.find_label("A"), Some(Pos::new(0, 2)));
+    assert_eq!(maze.find_label(" 2 "), Some(Pos::new(0, 1)));
+    assert_eq!(maze.find_label("Z"), None);
+
+    let unmarked = Maze::new_from_file("my_maze_sm").unwrap();
+    assert_eq!(unmarked.start(), None);
+    assert_eq!(unmarked.goals(), vec![]);
+}
+
+#[test]
+fn test_custom_scheme() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+    let scheme = MarkerScheme {
+        start: vec![String::from("A")],
+        goals: vec![String::from("S")],
+        blocked: vec![],
+        numbered_waypoints: false,
+    };
+
+    assert_eq!(maze.start_with(&scheme), Some(Pos::new(0, 2)));
+    assert_eq!(maze.goals_with(&scheme), vec![Pos::new(0, 0)]);
+    assert_eq!(maze.waypoints_with(&scheme), vec![]);
+    assert_eq!(
+        scheme.classify(" # "),
+        Some(Marker::Label(String::from("#")))
+    );
+
+    // Markers give a solver its endpoints
+    let path = maze.solve(maze.start().unwrap(), maze.goals()[0]).unwrap();
+    assert_eq!(path.goal(), Pos::new(1, 2));
+}
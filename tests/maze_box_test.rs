@@ -0,0 +1,68 @@
+use maze_parse::{CellGeometry, Generator, Maze};
+
+const BOX_MAZE: &str = "\
+┌───┬───┐
+│ A │ B │
+├───┴───┤
+│ C   D │
+└───────┘";
+
+const ASCII_MAZE: &str = "\
++---+---+
+| A | B |
++---+---+
+| C   D |
++---+---+";
+
+#[test]
+fn test_parse_box_mazes() {
+    let parsed = Maze::parse_box(BOX_MAZE).unwrap();
+    assert_eq!(parsed, Maze::parse_sm(ASCII_MAZE).unwrap());
+    assert_eq!(parsed, BOX_MAZE.parse::<Maze>().unwrap());
+
+    // Heavy and double lines are read as walls too
+    let parsed = Maze::parse_box("╔═══╗\n║ A ║\n╚═══╝").unwrap();
+    assert_eq!(parsed, Maze::parse_sm("+---+\n| A |\n+---+").unwrap());
+}
+
+#[test]
+fn test_render_box_mazes() {
+    let tests = vec![
+        BOX_MAZE,
+        "┌───┐\n│ A │\n└───┘",
+        "╶───┐\n  A │\n·   ╵",
+        "\
+┌───────────┬───┐
+│ S         │   │
+│   ╶───┐   ╵   │
+│       │     E │
+└───────┴───────┘",
+    ];
+
+    for input in tests {
+        assert_eq!(Maze::parse_box(input).unwrap().render_box(), input);
+    }
+
+    assert_eq!(Maze::parse_sm(ASCII_MAZE).unwrap().render_box(), BOX_MAZE);
+}
+
+#[test]
+fn test_box_round_trip_without_top_junctions() {
+    // Open top rows leave no `┬` on the first line to measure the cells by
+    let mut mazes = vec![
+        Maze::parse_sm("+---+---+\n| A     |\n+---+---+").unwrap(),
+        Maze::parse_sm("+---+---+---+\n|           |\n+---+   +---+\n|   | B |   |\n+---+---+---+")
+            .unwrap(),
+        Maze::parse_sm_with(
+            "+-----+-----+\n|           |\n|           |\n+-----+-----+\n|     |     |\n|     |     |\n+-----+-----+",
+            CellGeometry::new(5, 2),
+        )
+        .unwrap(),
+    ];
+    mazes.extend((0..4).map(|seed| Maze::generate(6, 4, Generator::BinaryTree, seed)));
+
+    for maze in mazes {
+        let rendered = maze.render_box();
+        assert_eq!(rendered.parse::<Maze>().unwrap(), maze, "\n{}", rendered);
+    }
+}
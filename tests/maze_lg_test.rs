@@ -1,4 +1,4 @@
-use crate::{cell::Cell, maze::Maze};
+use maze_parse::{Cell, Direction, Maze, Pos, Span, SyntaxError};
 
 #[test]
 fn test_parse_valid_lg_mazes() {
@@ -442,5 +442,78 @@ fn test_parse_valid_lg_mazes() {
 
 #[test]
 fn test_parse_invalid_lg_mazes() {
-    // TODO: ...
+    let tests = vec![
+        (
+            "+---++---+\n| A || B |\n+---++---",
+            SyntaxError::RaggedRow {
+                span: Span::at(3, 10),
+                cell: Pos::new(0, 1),
+                expected: 10,
+                found: 9,
+            },
+        ),
+        (
+            "+---++---+\n| A || B |",
+            SyntaxError::TruncatedCell {
+                span: Span::new(2, 1, 5),
+                cell: Pos::new(0, 0),
+            },
+        ),
+        (
+            "+---++---+\n| A || B |\n+---++- -+",
+            SyntaxError::InconsistentWall {
+                span: Span::new(3, 7, 9),
+                cell: Pos::new(0, 1),
+                side: Direction::Down,
+            },
+        ),
+        (
+            "+---+\n| A |\n+---+\n+---+\n| B :\n+---+",
+            SyntaxError::UnexpectedChar {
+                span: Span::at(5, 5),
+                cell: Pos::new(1, 0),
+                found: ':',
+                expected: "a wall or a space",
+            },
+        ),
+    ];
+
+    for (input, expected) in tests {
+        assert_eq!(Maze::parse_lg(input).unwrap_err(), expected);
+    }
+}
+
+#[test]
+fn test_parse_unicode_lg_mazes() {
+    let parsed = Maze::parse_lg("+---++---+\n| é ||🐭 |\n+---++---+").unwrap();
+    assert_eq!(parsed.cells[0][0].inner_text, " é ");
+    assert_eq!(parsed.cells[0][1].inner_text, "🐭 ");
+    assert!(parsed.cells[0][1].wall_left && parsed.cells[0][1].wall_right);
+}
+
+#[test]
+fn test_render_lg_mazes() {
+    let tests = vec![
+        "+---+\n| A |\n+---+",
+        "\
++---++---+
+| A  | B |
++---++---+
++   ++   +
+  C    D |
++---++---+",
+        "\
++---++---+
+| 東||   |
++---++---+",
+        include_str!("../my_maze_lg").trim_end_matches('\n'),
+    ];
+
+    for input in tests {
+        assert_eq!(Maze::parse_lg(input).unwrap().render_lg(), input);
+    }
+
+    // Shared walls and corners are written out by both neighbours
+    let maze = Maze::parse_sm("+---+---+\n| A   B |\n+---+---+").unwrap();
+    assert_eq!(maze.render_lg(), "+---++---+\n| A    B |\n+---++---+");
 }
@@ -1,4 +1,4 @@
-use crate::{cell::Cell, maze::Maze};
+use maze_parse::{Cell, Direction, Maze, Pos, Span, SyntaxError};
 
 #[test]
 fn test_parse_valid_sm_mazes() {
@@ -317,5 +317,132 @@ fn test_parse_valid_sm_mazes() {
 
 #[test]
 fn test_parse_invalid_sm_mazes() {
-    // TODO: ...
+    let tests = vec![
+        (
+            "+---+---+\n| A | B |\n+---+",
+            SyntaxError::RaggedRow {
+                span: Span::new(3, 6, 9),
+                cell: Pos::new(0, 1),
+                expected: 9,
+                found: 5,
+            },
+        ),
+        (
+            "+---+---+\n| A | B |\n+---+---+\n| C | D |",
+            SyntaxError::TruncatedCell {
+                span: Span::new(4, 1, 5),
+                cell: Pos::new(1, 0),
+            },
+        ),
+        (
+            "+---+---+\n| A # B |\n+---+---+",
+            SyntaxError::UnexpectedChar {
+                span: Span::at(2, 5),
+                cell: Pos::new(0, 0),
+                found: '#',
+                expected: "a wall or a space",
+            },
+        ),
+        (
+            "+---+---+\n| A | B |\n+---+---*",
+            SyntaxError::UnexpectedChar {
+                span: Span::at(3, 9),
+                cell: Pos::new(0, 1),
+                found: '*',
+                expected: "a corner or a space",
+            },
+        ),
+        (
+            "+---+---+\n| A | B |\n+---+ --+",
+            SyntaxError::InconsistentWall {
+                span: Span::new(3, 6, 8),
+                cell: Pos::new(0, 1),
+                side: Direction::Down,
+            },
+        ),
+        (
+            "+---+\n| A |\n|   \n+---+",
+            SyntaxError::RaggedRow {
+                span: Span::at(3, 5),
+                cell: Pos::new(0, 0),
+                expected: 5,
+                found: 4,
+            },
+        ),
+    ];
+
+    for (input, expected) in tests {
+        let err = Maze::parse_sm(input).unwrap_err();
+        assert_eq!(err, expected);
+        assert_eq!(err.span(), expected.span());
+        assert_eq!(err.cell(), expected.cell());
+
+        let err = input.parse::<Maze>().unwrap_err();
+        assert_eq!(err.sm_err, Some(expected));
+        assert_eq!(err.lg_err, None);
+    }
+}
+
+#[test]
+fn test_parse_unicode_sm_mazes() {
+    let tests = vec![
+        ("+---+---+\n| é | ü |\n+---+---+", vec![" é ", " ü "]),
+        ("+---+---+\n|🐭 | ß |\n+---+---+", vec!["🐭 ", " ß "]),
+        (
+            "+---+---+\n| e\u{301} |東 |\n+---+---+",
+            vec![" e\u{301} ", "東 "],
+        ),
+    ];
+
+    for (input, expected) in tests {
+        let parsed = Maze::parse_sm(input).unwrap();
+        assert_eq!(parsed.cells.len(), 1);
+
+        let row = &parsed.cells[0];
+        assert_eq!(
+            row.iter()
+                .map(|cell| cell.inner_text.as_str())
+                .collect::<Vec<_>>(),
+            expected,
+        );
+        assert!(row
+            .iter()
+            .all(|cell| cell.wall_left && cell.wall_right && cell.wall_top && cell.wall_bottom));
+    }
+
+    // A wide character straddling a wall column cannot be split into cells
+    assert!(Maze::parse_sm("+---+---+\n|  🐭  |\n+---+---+").is_err());
+}
+
+#[test]
+fn test_render_sm_mazes() {
+    let tests = vec![
+        "+---+\n| A |\n+---+",
+        "\
++---+---+
+| A | B |
++---+---+
+| C   D |
++---+---+",
+        "\
++   +---+
+  A     |
++---+   +",
+        "\
++---+---+
+| 東|   |
++---+---+",
+        include_str!("../my_maze_sm").trim_end_matches('\n'),
+        "",
+    ];
+
+    for input in tests {
+        let maze = Maze::parse_sm(input).unwrap();
+        assert_eq!(maze.render_sm(), input);
+        assert_eq!(maze.to_string(), input);
+    }
+
+    // Walls drawn by only one of two neighbours are merged when rendered
+    let maze = Maze::parse_lg("+---++---+\n| A  | B |\n+---++---+").unwrap();
+    assert_eq!(maze.render_sm(), "+---+---+\n| A | B |\n+---+---+");
 }
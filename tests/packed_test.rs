@@ -0,0 +1,143 @@
+use std::mem::size_of;
+
+use maze_parse::{
+    Cell, CellFlags, CellGeometry, Corner, Direction, Generator, Maze, MazeFormat, PackedMaze, Pos,
+};
+
+#[test]
+fn test_pack_round_trip() {
+    for path in [
+        "my_maze_sm",
+        "my_maze_lg",
+        "my_maze_sm_weighted",
+        "my_maze_lg_weighted",
+    ] {
+        let maze = Maze::new_from_file(path).unwrap();
+        let packed = maze.pack();
+        assert_eq!(
+            (packed.width(), packed.height()),
+            (maze.width(), maze.height())
+        );
+        assert_eq!(packed.geometry(), maze.geometry());
+        assert_eq!(packed.unpack(), maze);
+
+        for (pos, cell) in maze.iter_row_major() {
+            assert_eq!(packed.get(pos).as_ref(), Some(cell));
+            assert_eq!(packed.flags(pos), Some(CellFlags::from(cell)));
+            assert_eq!(packed.inner_text(pos), Some(cell.inner_text.as_str()));
+        }
+    }
+}
+
+#[test]
+fn test_parse_packed() {
+    let wide = "+-----+-----+\n| A   |     |\n|     |   B |\n+-----+-----+";
+    let generated = Maze::generate(40, 30, Generator::Prim, 2).render_box();
+
+    for input in [
+        include_str!("../my_maze_sm"),
+        include_str!("../my_maze_lg"),
+        include_str!("../my_maze_sm_weighted"),
+        include_str!("../my_maze_lg_weighted"),
+        wide,
+        &generated,
+        "",
+    ] {
+        let packed = input.parse::<PackedMaze>().unwrap();
+        let maze = input.parse::<Maze>().unwrap();
+        assert_eq!(packed, maze.pack());
+        assert_eq!(packed.unpack(), maze);
+    }
+
+    let geometry = CellGeometry::new(5, 2);
+    assert_eq!(
+        PackedMaze::parse_as(wide, MazeFormat::Small, geometry),
+        Maze::parse_as(wide, MazeFormat::Small, geometry).map(|maze| maze.pack())
+    );
+
+    // Errors are the same as when parsing a `Maze`
+    let ragged = "+---+---+\n| A |\n+---+---+";
+    assert_eq!(
+        ragged.parse::<PackedMaze>().unwrap_err().to_string(),
+        ragged.parse::<Maze>().unwrap_err().to_string()
+    );
+    assert_eq!(
+        PackedMaze::parse_as(ragged, MazeFormat::Small, CellGeometry::default()).unwrap_err(),
+        Maze::parse_sm(ragged).unwrap_err()
+    );
+}
+
+#[test]
+fn test_pack_pads_ragged_rows() {
+    // Mazes built by hand can have short rows
+    let mut maze = Maze::parse_sm("+---+---+\n| A |   |\n+---+---+\n|   |   |\n+---+---+").unwrap();
+    maze.cells[1].truncate(1);
+
+    let packed = maze.pack();
+    assert_eq!((packed.width(), packed.height()), (2, 2));
+    assert_eq!(packed.flags(Pos::new(1, 1)), Some(CellFlags::empty()));
+    assert_eq!(packed.inner_text(Pos::new(1, 1)), Some("   "));
+    assert_eq!(packed.get(Pos::new(2, 0)), None);
+    assert_eq!(packed.get(Pos::new(0, 2)), None);
+}
+
+#[test]
+fn test_cell_flags() {
+    assert_eq!(size_of::<CellFlags>(), 1);
+
+    let cell = Cell {
+        wall_top: true,
+        wall_left: true,
+        corner_top_left: true,
+        corner_bottom_right: true,
+        inner_text: String::from(" A "),
+        ..Cell::default()
+    };
+    let flags = CellFlags::from(&cell);
+    assert_eq!(flags.bits(), 0b0101_1001);
+    assert!(flags.has_wall(Direction::Up) && flags.has_wall(Direction::Left));
+    assert!(!flags.has_wall(Direction::Right) && !flags.has_wall(Direction::Down));
+    assert!(flags.has_corner(Corner::TopLeft) && flags.has_corner(Corner::BottomRight));
+    assert!(!flags.has_corner(Corner::TopRight));
+    assert_eq!(flags.to_cell(" A "), cell);
+
+    let mut flags = CellFlags::empty();
+    flags.set(CellFlags::WALL_RIGHT, true);
+    flags.set(CellFlags::wall(Direction::Down), true);
+    flags.set(CellFlags::WALL_RIGHT, false);
+    assert_eq!(flags, CellFlags::WALL_BOTTOM);
+    assert!(CellFlags::all().contains(flags));
+}
+
+#[test]
+fn test_packed_navigation() {
+    let maze = Maze::generate(12, 9, Generator::Kruskal, 7);
+    let packed = maze.pack();
+
+    for (pos, _) in maze.iter_row_major() {
+        for direction in Direction::ALL {
+            assert_eq!(
+                packed.can_move(pos, direction),
+                maze.can_move(pos, direction)
+            );
+        }
+        assert_eq!(
+            packed.open_neighbors(pos).collect::<Vec<_>>(),
+            maze.open_neighbors(pos).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn test_pack_interns_labels() {
+    let maze =
+        Maze::parse_sm("+---+---+---+\n| ~ | ~ | S |\n+---+---+---+\n|   | ~ |   |\n+---+---+---+")
+            .unwrap();
+    let packed = maze.pack();
+
+    assert_eq!(packed.inner_text(Pos::new(0, 0)), Some(" ~ "));
+    assert_eq!(packed.inner_text(Pos::new(1, 1)), Some(" ~ "));
+    assert_eq!(packed.inner_text(Pos::new(0, 2)), Some(" S "));
+    assert_eq!(packed.inner_text(Pos::new(1, 0)), Some("   "));
+    assert_eq!(packed.unpack(), maze);
+}
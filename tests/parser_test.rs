@@ -0,0 +1,47 @@
+use maze_parse::ContentParser;
+
+#[test]
+fn test_parser_navigation() {
+    let mut parser = ContentParser::new("+---+\r\n| A |\n+---+");
+
+    assert_eq!(parser.line_count(), 3);
+    assert_eq!(parser.curr_char().unwrap(), '+');
+
+    parser.next_line().unwrap();
+    assert_eq!(parser.end_of_line().unwrap(), '|');
+    assert_eq!(parser.curr_pos(), (2, 5));
+
+    parser.move_cols(-2).unwrap();
+    assert_eq!(parser.curr_char().unwrap(), 'A');
+    assert!(parser.move_cols(3).is_err());
+    assert_eq!(parser.curr_pos(), (2, 3));
+
+    parser.move_pos(1, -2).unwrap();
+    assert_eq!(parser.slice(5).unwrap(), "+---+");
+    assert!(parser.slice(6).is_err());
+    assert!(parser.move_lines(1).is_err());
+
+    assert!(parser.go_to(1, 6).is_err());
+    assert_eq!(parser.go_to_pos((1, 5)).unwrap(), '+');
+}
+
+#[test]
+fn test_parser_columns_are_display_width() {
+    let mut parser = ContentParser::new("|🐭é|\n|e\u{301}x|");
+
+    assert_eq!(parser.end_of_line().unwrap(), '|');
+    assert_eq!(parser.curr_pos(), (1, 5));
+
+    assert_eq!(parser.go_to(1, 2).unwrap(), '🐭');
+    assert_eq!(parser.slice(3).unwrap(), "🐭é");
+    assert_eq!(parser.go_to(1, 3).unwrap(), '🐭');
+    assert!(parser.slice(2).is_err());
+    parser.move_cols(1).unwrap();
+    assert_eq!(parser.curr_char().unwrap(), 'é');
+
+    parser.go_to(1, 1).unwrap();
+    assert!(parser.slice(2).is_err());
+
+    parser.go_to(2, 2).unwrap();
+    assert_eq!(parser.slice(2).unwrap(), "e\u{301}x");
+}
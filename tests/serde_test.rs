@@ -0,0 +1,138 @@
+use maze_parse::{EdgeList, Edit, EditLog, Generator, Maze, Pos, EDGE_LIST_VERSION};
+use serde_json::{json, Value};
+
+fn schema() -> jsonschema::Validator {
+    let schema = serde_json::from_str(include_str!("../schema/maze.v1.schema.json")).unwrap();
+    jsonschema::validator_for(&schema).unwrap()
+}
+
+fn fixtures() -> Vec<Maze> {
+    let mut mazes = ["my_maze_sm", "my_maze_lg", "my_maze_sm_weighted"]
+        .into_iter()
+        .map(|path| Maze::new_from_file(path).unwrap())
+        .collect::<Vec<_>>();
+    mazes.push(Maze::generate(7, 5, Generator::Wilson, 2));
+    mazes.push(Maze::parse_lg("+---++---+\n| A  | B |\n+---++   +").unwrap());
+    mazes
+}
+
+#[test]
+fn test_serialize_cells() {
+    let schema = schema();
+
+    for maze in fixtures() {
+        let value = serde_json::to_value(&maze).unwrap();
+        assert!(schema.is_valid(&value), "{}", value);
+        assert_eq!(serde_json::from_value::<Maze>(value).unwrap(), maze);
+    }
+
+    let maze = "+---+\n| A |\n+---+".parse::<Maze>().unwrap();
+    assert_eq!(
+        serde_json::to_value(&maze).unwrap(),
+        json!({"cells": [[{
+            "wall_top": true,
+            "wall_bottom": true,
+            "wall_left": true,
+            "wall_right": true,
+            "corner_top_left": true,
+            "corner_top_right": true,
+            "corner_bottom_left": true,
+            "corner_bottom_right": true,
+            "inner_text": " A ",
+        }]]}),
+    );
+}
+
+#[test]
+fn test_serialize_edge_list() {
+    let schema = schema();
+
+    for maze in fixtures() {
+        let value = serde_json::to_value(maze.to_edge_list()).unwrap();
+        assert!(schema.is_valid(&value), "{}", value);
+
+        let edges = serde_json::from_value::<EdgeList>(value).unwrap();
+        assert_eq!(edges, maze.to_edge_list());
+    }
+
+    let maze = "+---+---+\n| S     |\n+---+   +".parse::<Maze>().unwrap();
+    assert_eq!(
+        serde_json::to_value(maze.to_edge_list()).unwrap(),
+        json!({
+            "version": EDGE_LIST_VERSION,
+            "width": 2,
+            "height": 1,
+            "cell_width": 3,
+            "cell_height": 1,
+            "horizontal_walls": [[0, 0], [0, 1], [1, 0]],
+            "vertical_walls": [[0, 0], [0, 2]],
+            "labels": [{"row": 0, "col": 0, "text": " S "}],
+        }),
+    );
+}
+
+#[test]
+fn test_schema_rejects_invalid_documents() {
+    let schema = schema();
+    let valid =
+        serde_json::to_value(Maze::generate(3, 3, Generator::Prim, 1).to_edge_list()).unwrap();
+    assert!(schema.is_valid(&valid));
+
+    let invalid: Vec<Value> = vec![
+        json!({}),
+        json!({"cells": [[{"wall_top": true}]]}),
+        json!({"cells": [], "extra": 1}),
+        {
+            let mut v = valid.clone();
+            v["version"] = json!(2);
+            v
+        },
+        {
+            let mut v = valid.clone();
+            v["horizontal_walls"] = json!([[0, 0, 0]]);
+            v
+        },
+        {
+            let mut v = valid.clone();
+            v["width"] = json!(-1);
+            v
+        },
+    ];
+    for value in invalid {
+        assert!(!schema.is_valid(&value), "{}", value);
+    }
+}
+
+#[test]
+fn test_serialize_edit_log() {
+    let original = Maze::new_from_file("my_maze_sm").unwrap();
+    let mut maze = original.clone();
+    let mut log = EditLog::new();
+
+    log.begin("widen");
+    log.apply(&mut maze, Edit::InsertCol(1)).unwrap();
+    log.apply(
+        &mut maze,
+        Edit::SetLabel {
+            pos: Pos::new(0, 1),
+            label: String::from("X"),
+        },
+    )
+    .unwrap();
+    log.commit();
+    log.apply(&mut maze, Edit::RemoveRow(0)).unwrap();
+    log.undo(&mut maze);
+
+    let json = serde_json::to_string(&log).unwrap();
+    let mut saved = serde_json::from_str::<EditLog>(&json).unwrap();
+    assert_eq!(saved, log);
+
+    // The saved session replays against a fresh parse and still undoes and redoes
+    let mut replayed = Maze::new_from_file("my_maze_sm").unwrap();
+    saved.replay(&mut replayed).unwrap();
+    assert_eq!(replayed, maze);
+    assert_eq!(saved.redo(&mut replayed).unwrap(), Some("remove row 0"));
+    assert_eq!(saved.undo(&mut replayed), Some("remove row 0"));
+    assert_eq!(saved.undo(&mut replayed), Some("widen"));
+    assert_eq!(replayed, original);
+}
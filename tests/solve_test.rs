@@ -0,0 +1,78 @@
+use maze_parse::{Direction, Maze, Pos};
+
+const MAZE: &str = "\
++---+---+---+
+| S     |   |
++---+   +   +
+|       |   |
++   +---+   +
+|         E |
++---+---+---+";
+
+#[test]
+fn test_solve_shortest_path() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+    let path = maze.solve(Pos::new(0, 0), Pos::new(2, 2)).unwrap();
+
+    assert_eq!(
+        path.directions,
+        vec![
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+            Direction::Right,
+        ],
+    );
+    assert_eq!(
+        path.positions,
+        vec![
+            Pos::new(0, 0),
+            Pos::new(0, 1),
+            Pos::new(1, 1),
+            Pos::new(1, 0),
+            Pos::new(2, 0),
+            Pos::new(2, 1),
+            Pos::new(2, 2),
+        ],
+    );
+    assert_eq!(path.len(), 6);
+    assert_eq!(path.start(), Pos::new(0, 0));
+    assert_eq!(path.goal(), Pos::new(2, 2));
+
+    // The shortest of the two routes around the fixture's loop
+    let maze = Maze::new_from_file("my_maze_sm").unwrap();
+    assert_eq!(maze.solve(Pos::new(0, 0), Pos::new(3, 3)).unwrap().len(), 6);
+}
+
+#[test]
+fn test_solve_trivial_and_unreachable() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+
+    let path = maze.solve(Pos::new(1, 2), Pos::new(1, 2)).unwrap();
+    assert!(path.is_empty());
+    assert_eq!(path.positions, vec![Pos::new(1, 2)]);
+
+    let walled = "+---+---+\n| A | B |\n+---+---+".parse::<Maze>().unwrap();
+    assert_eq!(walled.solve(Pos::new(0, 0), Pos::new(0, 1)), None);
+    assert_eq!(maze.solve(Pos::new(0, 0), Pos::new(3, 0)), None);
+}
+
+#[test]
+fn test_overlay_path() {
+    let maze = MAZE.parse::<Maze>().unwrap();
+    let path = maze.solve(Pos::new(0, 0), Pos::new(2, 2)).unwrap();
+
+    assert_eq!(
+        maze.overlay_path(&path).to_string(),
+        "\
++---+---+---+
+| S   * |   |
++---+   +   +
+| *   * |   |
++   +---+   +
+| *   *   E |
++---+---+---+",
+    );
+}
@@ -0,0 +1,134 @@
+use maze_parse::{Direction, Generator, Maze, MazeStats, Pos};
+
+#[test]
+fn test_stats_fixtures() {
+    let expected = MazeStats {
+        width: 4,
+        height: 4,
+        cells: 16,
+        isolated: 0,
+        dead_ends: 5,
+        corridors: 8,
+        t_junctions: 3,
+        crossroads: 0,
+        passages: 15,
+        components: 1,
+        loops: 0,
+        diameter: 11,
+        diameter_exact: true,
+        solution_length: Some(6),
+        difficulty: Some(6.0 / 6.0 * (1.0 + 5.0 / 16.0)),
+    };
+
+    for path in ["my_maze_sm", "my_maze_lg"] {
+        assert_eq!(Maze::new_from_file(path).unwrap().stats(), expected);
+    }
+}
+
+#[test]
+fn test_stats_loops_and_components() {
+    let maze = "\
++---+---+---+---+
+| S         |   |
++   +   +   +---+
+|         E | # |
++---+---+---+---+"
+        .parse::<Maze>()
+        .unwrap();
+    let stats = maze.stats();
+
+    assert_eq!(stats.t_junctions, 2);
+    assert_eq!(stats.crossroads, 0);
+    assert_eq!(stats.isolated, 2);
+    assert_eq!(stats.passages, 7);
+    assert_eq!(stats.components, 3);
+    assert_eq!(stats.loops, 2);
+    assert_eq!(stats.diameter, 3);
+    assert!(!stats.diameter_exact);
+    assert_eq!(stats.solution_length, Some(3));
+    assert_eq!(stats.difficulty, Some(1.0));
+
+    let open = "\
++---+---+---+
+|           |
++   +   +   +
+|           |
++   +   +   +
+|           |
++---+---+---+"
+        .parse::<Maze>()
+        .unwrap()
+        .stats();
+    assert_eq!(open.crossroads, 1);
+    assert_eq!(open.loops, 4);
+    assert_eq!(open.diameter, 4);
+    assert_eq!(open.dead_ends, 0);
+
+    let sealed = "+---+---+\n| S | E |\n+---+---+".parse::<Maze>().unwrap();
+    assert_eq!(sealed.stats().solution_length, None);
+    assert_eq!(sealed.stats().difficulty, None);
+}
+
+#[test]
+fn test_stats_generated() {
+    for generator in Generator::ALL {
+        let stats = Maze::generate(15, 10, generator, 3).stats();
+        assert_eq!(stats.loops, 0, "{}", generator);
+        assert_eq!(stats.components, 1, "{}", generator);
+        assert_eq!(stats.passages, 149, "{}", generator);
+        assert!(stats.diameter >= stats.solution_length.unwrap());
+        assert!(stats.difficulty.unwrap() >= 1.0);
+    }
+}
+
+#[test]
+fn test_stats_diameter_estimate() {
+    // With loops, two sweeps can stop short of the longest shortest path
+    let maze = "\
++---+---+---+---+---+---+
+|       |           |   |
++   +   +---+---+   +   +
+|   |                   |
++   +   +---+   +   +   +
+|       |       |   |   |
++---+   +   +---+---+   +
+|                       |
++---+---+   +---+   +   +
+|               |       |
++---+---+---+---+---+---+"
+        .parse::<Maze>()
+        .unwrap();
+
+    let estimate = maze.stats();
+    assert_eq!((estimate.diameter, estimate.diameter_exact), (9, false));
+    let exact = maze.stats_with_diameter();
+    assert_eq!((exact.diameter, exact.diameter_exact), (10, true));
+    assert_eq!(
+        MazeStats {
+            diameter: 9,
+            diameter_exact: false,
+            ..exact
+        },
+        estimate
+    );
+
+    // Without loops the estimate is already exact
+    let perfect = Maze::generate(15, 10, Generator::Wilson, 5);
+    assert!(perfect.stats().diameter_exact);
+    assert_eq!(perfect.stats(), perfect.stats_with_diameter());
+}
+
+#[test]
+fn test_stats_large_maze_with_loops() {
+    let mut maze = Maze::generate(300, 300, Generator::Kruskal, 1);
+    for r in (0..300).step_by(7) {
+        for c in (0..299).step_by(5) {
+            maze.carve(Pos::new(r, c), Direction::Right).unwrap();
+        }
+    }
+
+    let stats = maze.stats();
+    assert!(stats.loops > 0);
+    assert!(!stats.diameter_exact);
+    assert!(stats.diameter >= stats.solution_length.unwrap());
+}
@@ -0,0 +1,93 @@
+use maze_parse::{Corner, Direction, Inconsistency, Maze, Pos, StrictParseError};
+
+#[test]
+fn test_validate_consistent_mazes() {
+    for path in ["my_maze_sm", "my_maze_lg"] {
+        let maze = Maze::new_from_file(path).unwrap();
+        assert_eq!(maze.validate(), vec![], "{}", path);
+    }
+}
+
+#[test]
+fn test_validate_inconsistent_mazes() {
+    let tests = vec![
+        (
+            "\
++---++---+
+| A ||   |
++---++---+
++---++   +
+| C  | D |
++---++---+",
+            vec![
+                Inconsistency::OneSidedWall {
+                    cell: Pos::new(0, 1),
+                    side: Direction::Down,
+                },
+                Inconsistency::OneSidedWall {
+                    cell: Pos::new(1, 1),
+                    side: Direction::Left,
+                },
+            ],
+        ),
+        (
+            "\
++---++---+
+| A || B |
++---+ ---+",
+            vec![
+                Inconsistency::MissingCorner {
+                    cell: Pos::new(0, 1),
+                    corner: Corner::BottomLeft,
+                },
+                Inconsistency::WallWithoutCorners {
+                    cell: Pos::new(0, 1),
+                    side: Direction::Down,
+                },
+                Inconsistency::WallWithoutCorners {
+                    cell: Pos::new(0, 1),
+                    side: Direction::Left,
+                },
+            ],
+        ),
+        (
+            " --- \n| A |\n --- ",
+            vec![
+                Inconsistency::WallWithoutCorners {
+                    cell: Pos::new(0, 0),
+                    side: Direction::Up,
+                },
+                Inconsistency::WallWithoutCorners {
+                    cell: Pos::new(0, 0),
+                    side: Direction::Right,
+                },
+                Inconsistency::WallWithoutCorners {
+                    cell: Pos::new(0, 0),
+                    side: Direction::Down,
+                },
+                Inconsistency::WallWithoutCorners {
+                    cell: Pos::new(0, 0),
+                    side: Direction::Left,
+                },
+            ],
+        ),
+    ];
+
+    for (input, expected) in tests {
+        assert_eq!(input.parse::<Maze>().unwrap().validate(), expected);
+
+        match Maze::parse_strict(input) {
+            Err(StrictParseError::Inconsistent(findings)) => assert_eq!(findings, expected),
+            other => panic!("expected inconsistent maze, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_parse_strict() {
+    assert!(Maze::parse_strict("+---+---+\n| A   B |\n+---+---+").is_ok());
+    assert!(matches!(
+        Maze::parse_strict("+---+\n| A |"),
+        Err(StrictParseError::Parse(_)),
+    ));
+}
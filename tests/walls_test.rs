@@ -0,0 +1,130 @@
+use maze_parse::{Corner, Direction, Generator, Inconsistency, Maze, MergePolicy, Pos, WallGrid};
+
+const LG_ONE_SIDED: &str = "\
++---++---+
+| A  | B |
++---++   +
++---++---+
+| C || D |
++---++---+";
+
+#[test]
+fn test_wall_grid_round_trip() {
+    for path in [
+        "my_maze_sm",
+        "my_maze_lg",
+        "my_maze_sm_weighted",
+        "my_maze_lg_weighted",
+    ] {
+        let maze = Maze::new_from_file(path).unwrap();
+        let converted = maze.to_wall_grid(MergePolicy::Error).unwrap();
+        assert!(converted.conflicts.is_empty());
+        let grid = converted.value;
+        assert_eq!((grid.width(), grid.height()), (maze.width(), maze.height()));
+        assert_eq!(grid.to_maze(), maze, "{}", path);
+        assert_eq!(grid.to_string(), maze.render_sm());
+    }
+
+    // Cells keep their geometry
+    let maze: Maze = "+-----+-----+\n|  A  |  B  |\n|     |     |\n+-----+-----+"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        Maze::from(&maze.to_wall_grid(MergePolicy::Error).unwrap().value),
+        maze
+    );
+}
+
+#[test]
+fn test_wall_grid_merge_policies() {
+    let maze = Maze::parse_lg(LG_ONE_SIDED).unwrap();
+
+    let conflicts = vec![
+        Inconsistency::OneSidedWall {
+            cell: Pos::new(0, 1),
+            side: Direction::Left,
+        },
+        Inconsistency::OneSidedWall {
+            cell: Pos::new(1, 1),
+            side: Direction::Up,
+        },
+    ];
+
+    let converted = maze.to_wall_grid(MergePolicy::Union).unwrap();
+    assert_eq!(converted.conflicts, conflicts);
+    let union = converted.value;
+    assert!(union.has_wall(Pos::new(0, 0), Direction::Right));
+    assert!(union.has_wall(Pos::new(0, 1), Direction::Left));
+    assert!(union.has_wall(Pos::new(0, 1), Direction::Down));
+    assert!(union.has_wall(Pos::new(1, 1), Direction::Up));
+    assert!(union.to_maze().validate().is_empty());
+
+    let converted = maze.to_wall_grid(MergePolicy::Intersection).unwrap();
+    assert_eq!(converted.conflicts, conflicts);
+    let intersection = converted.value;
+    assert!(!intersection.has_wall(Pos::new(0, 0), Direction::Right));
+    assert!(!intersection.has_wall(Pos::new(0, 1), Direction::Down));
+    assert!(intersection.has_wall(Pos::new(1, 0), Direction::Right));
+
+    let err = maze.to_wall_grid(MergePolicy::Error).unwrap_err();
+    assert_eq!(err.conflicts(), conflicts);
+
+    // Corners are conflicts too
+    let mut maze = Maze::parse_sm("+---+---+\n| A | B |\n+---+---+").unwrap();
+    maze.cells[0][1].corner_top_left = false;
+    let missing = vec![Inconsistency::MissingCorner {
+        cell: Pos::new(0, 1),
+        corner: Corner::TopLeft,
+    }];
+    let err = maze.to_wall_grid(MergePolicy::Error).unwrap_err();
+    assert_eq!(err.conflicts(), missing);
+    let converted = maze.to_wall_grid(MergePolicy::Union).unwrap();
+    assert_eq!(converted.conflicts, missing);
+    assert!(converted.value.has_corner(Pos::new(0, 1), Corner::TopLeft));
+    let converted = maze.to_wall_grid(MergePolicy::Intersection).unwrap();
+    assert_eq!(converted.conflicts, missing);
+    assert!(!converted.value.has_corner(Pos::new(0, 0), Corner::TopRight));
+}
+
+#[test]
+fn test_wall_grid_edits_both_sides() {
+    let mut grid = WallGrid::new(2, 2);
+    assert_eq!(
+        grid.to_string(),
+        "+---+---+\n|   |   |\n+---+---+\n|   |   |\n+---+---+"
+    );
+
+    assert!(grid.set_wall(Pos::new(0, 0), Direction::Right, false));
+    assert!(!grid.has_wall(Pos::new(0, 1), Direction::Left));
+    assert!(grid.can_move(Pos::new(0, 1), Direction::Left));
+    assert!(grid.set_wall(Pos::new(1, 1), Direction::Up, false));
+    assert!(grid.set_label(Pos::new(0, 0), " S "));
+    assert!(!grid.set_wall(Pos::new(2, 0), Direction::Up, false));
+    assert!(!grid.set_label(Pos::new(0, 2), " X "));
+
+    let maze = grid.to_maze();
+    assert_eq!(
+        maze.render_sm(),
+        "+---+---+\n| S     |\n+---+   +\n|   |   |\n+---+---+"
+    );
+    assert!(maze.validate().is_empty());
+    assert_eq!(grid.label(Pos::new(0, 0)), Some(" S "));
+    assert_eq!(grid.label(Pos::new(2, 0)), None);
+
+    // Border walls have no neighbour to move to
+    assert!(grid.set_wall(Pos::new(0, 0), Direction::Left, false));
+    assert!(!grid.can_move(Pos::new(0, 0), Direction::Left));
+}
+
+#[test]
+fn test_wall_grid_navigation() {
+    let maze = Maze::generate(10, 7, Generator::Wilson, 3);
+    let grid = maze.to_wall_grid(MergePolicy::Error).unwrap().value;
+
+    for (pos, _) in maze.iter_row_major() {
+        assert_eq!(
+            grid.open_neighbors(pos).collect::<Vec<_>>(),
+            maze.open_neighbors(pos).collect::<Vec<_>>()
+        );
+    }
+}
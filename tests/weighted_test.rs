@@ -0,0 +1,87 @@
+use maze_parse::{CostModel, Direction, Maze, Pos};
+
+const ROOM: &str = "\
++---+---+---+
+| S   9   E |
++   +   +   +
+|    ~~~    |
++---+---+---+";
+
+#[test]
+fn test_cost_model() {
+    let model = CostModel::default();
+    assert_eq!(model.cost("   "), Some(1));
+    assert_eq!(model.cost(" A "), Some(1));
+    assert_eq!(model.cost(" 5 "), Some(5));
+    assert_eq!(model.cost("12 "), Some(12));
+    assert_eq!(model.cost("~~~"), Some(3));
+    assert_eq!(model.cost(" ~ "), Some(3));
+    assert_eq!(model.cost("~#~"), Some(1));
+    assert_eq!(model.cost(" # "), None);
+    assert_eq!(model.cost("###"), None);
+
+    let model = CostModel {
+        default_cost: 2,
+        numeric_weights: false,
+        terrain: vec![('.', Some(0))],
+    };
+    assert_eq!(model.cost(" 5 "), Some(2));
+    assert_eq!(model.cost("..."), Some(0));
+    assert_eq!(model.cost("###"), Some(2));
+}
+
+#[test]
+fn test_weighted_fixtures() {
+    let model = CostModel::default();
+    let sm = Maze::new_from_file("my_maze_sm_weighted").unwrap();
+    let lg = Maze::new_from_file("my_maze_lg_weighted").unwrap();
+    assert_eq!(sm, lg);
+
+    for maze in [&sm, &lg] {
+        let start = Pos::new(0, 0);
+        let goal = Pos::new(3, 3);
+
+        let dijkstra = maze.solve_dijkstra(start, goal, &model).unwrap();
+        assert_eq!(dijkstra.cost, 2 + 3 + 5 + 1 + 3 + 1);
+        assert_eq!(dijkstra.path, maze.solve(start, goal).unwrap());
+        assert_eq!(maze.solve_astar(start, goal, &model).unwrap(), dijkstra);
+
+        // The only way into the bottom middle passes the impassable `###` cell
+        for goal in [Pos::new(3, 1), Pos::new(3, 2)] {
+            assert!(maze.solve(start, goal).is_some());
+            assert_eq!(maze.solve_dijkstra(start, goal, &model), None);
+            assert_eq!(maze.solve_astar(start, goal, &model), None);
+        }
+        assert_eq!(maze.cost_with(Pos::new(2, 2), &model), None);
+        assert_eq!(maze.cost_with(Pos::new(9, 9), &model), None);
+    }
+}
+
+#[test]
+fn test_weighted_detour() {
+    let maze = ROOM.parse::<Maze>().unwrap();
+    let model = CostModel::default();
+    let (start, goal) = (maze.start().unwrap(), maze.goals()[0]);
+
+    // Straight across costs 9 + 1; around the bottom costs 1 + 3 + 1 + 1
+    assert_eq!(maze.solve(start, goal).unwrap().len(), 2);
+    for weighted in [
+        maze.solve_dijkstra(start, goal, &model).unwrap(),
+        maze.solve_astar(start, goal, &model).unwrap(),
+    ] {
+        assert_eq!(weighted.cost, 6);
+        assert_eq!(
+            weighted.path.directions,
+            vec![
+                Direction::Down,
+                Direction::Right,
+                Direction::Right,
+                Direction::Up,
+            ],
+        );
+    }
+
+    let path = maze.solve_dijkstra(start, start, &model).unwrap();
+    assert_eq!(path.cost, 0);
+    assert!(path.path.is_empty());
+}